@@ -1,12 +1,78 @@
 // Game constants
 pub const GAME_SEED: &[u8] = b"game";
+// Layout version of the games this build reads and writes
+pub const GAME_VERSION: u8 = 1;
+// GameState account discriminators. From v1 on it is the hash of
+// `account:VersionedGameState` followed by the version byte, so Anchor refuses
+// games in any other version; Anchor's default `account:GameState` marks the
+// original layout (see `legacy`)
+pub const VERSIONED_GAME_PREFIX: &[u8] = &[213, 231, 181, 159, 211, 91, 134, 5];
+pub const GAME_DISCRIMINATOR: &[u8] = &[213, 231, 181, 159, 211, 91, 134, 5, GAME_VERSION];
+pub const LEGACY_GAME_DISCRIMINATOR: &[u8] = &[144, 94, 208, 172, 248, 99, 134, 120];
 pub const TREASURY_SEED: &[u8] = b"treasury";
-pub const MAX_PLAYERS: usize = 4;
+pub const CONFIG_SEED: &[u8] = b"config";
+pub const ESCROW_SEED: &[u8] = b"escrow";
+pub const BANKROLL_SEED: &[u8] = b"bankroll";
+pub const SESSION_SEED: &[u8] = b"session";
+pub const PROFILE_SEED: &[u8] = b"profile";
+pub const SEASON_SEED: &[u8] = b"season";
+pub const TOURNAMENT_SEED: &[u8] = b"tournament";
+pub const QUEUE_SEED: &[u8] = b"queue";
+pub const LOBBY_SEED: &[u8] = b"lobby";
+pub const COUNTER_SEED: &[u8] = b"counter";
+// Ids handed out by the game counter start here, above any caller-chosen id
+pub const FIRST_COUNTER_GAME_ID: u64 = 1 << 63;
+
+pub const MAX_PLAYERS: usize = 4; // Seats in the standard edition
+pub const SIX_PLAYER_SEATS: usize = 6; // Seats in the six-player edition
 pub const MIN_PLAYERS: usize = 2;
+pub const MAX_VALIDATORS: usize = 8;
+pub const MAX_SESSION_DURATION: i64 = 24 * 60 * 60; // 1 day
+pub const MAX_DISPLAY_NAME_LEN: usize = 32;
+pub const DEFAULT_RECOVERY_TIMEOUT: i64 = 60 * 60; // 1 hour without a committed turn
 pub const TILES_PER_PLAYER: usize = 14;
-pub const TOTAL_TILES: usize = 106; // 104 number tiles + 2 jokers
+pub const TOTAL_TILES: usize = 104 + MAX_JOKERS; // 104 number tiles + jokers
+pub const SIX_PLAYER_TILES: usize = 156 + MAX_JOKERS; // Three copies of each number tile
 pub const MIN_INITIAL_MELD: u8 = 30;
 pub const JOKER_SCORE: i16 = 30;
+pub const MAX_TABLE_MELDS: usize = 30;
+pub const MAX_MELD_TILES: usize = 13; // A full 1-13 run
+
+// Rule variant bounds
+pub const MAX_INITIAL_MELD: u8 = 50;
+pub const MIN_HAND_SIZE: u8 = 7;
+pub const MAX_HAND_SIZE: u8 = 16;
+pub const MAX_JOKERS: usize = 4;
+pub const SIX_PLAYER_JOKERS: u8 = 4; // The six-player edition's set
+pub const MAX_JOKER_PENALTY: u8 = 50;
+
+// Seasons
+pub const SEASON_LEADERBOARD_SIZE: usize = 10;
+pub const SEASON_POINTS_PLAYED: u32 = 1;
+pub const SEASON_POINTS_WON: u32 = 3;
+
+// Tournaments
+pub const MAX_TOURNAMENT_ENTRANTS: usize = 32;
+pub const MAX_TOURNAMENT_TABLES: usize = MAX_TOURNAMENT_ENTRANTS / 2;
+pub const MAX_TOURNAMENT_PAYOUTS: usize = 8;
+pub const NO_WINNER: u8 = u8::MAX; // Table result not reported yet
+pub const TOURNAMENT_REGISTRATION_WINDOW: i64 = 7 * 24 * 60 * 60; // Then anyone may cancel one short of players
+
+// Matchmaking
+pub const MAX_QUEUE_SIZE: usize = 16;
+pub const MAX_ALLOWLIST: usize = 8;
+pub const MAX_MATCH_ROUNDS: u8 = 20;
+pub const LOBBY_PAGE_SIZE: usize = 32;
+
+// Ratings
+pub const INITIAL_RATING: u16 = 1200;
+pub const MIN_RATING: u16 = 100;
+pub const ELO_K: i32 = 32;
+// Expected Elo score (per mille) for a rating lead of 0, 25, 50, ... 800 points
+pub const ELO_EXPECTED_PERMILLE: [i32; 33] = [
+    500, 536, 571, 606, 640, 673, 703, 733, 760, 785, 808, 830, 849, 867, 882, 896, 909, 920, 930,
+    939, 947, 954, 960, 965, 969, 973, 977, 980, 983, 985, 987, 989, 990,
+];
 
 // Entry fee and house settings
 pub const ENTRY_FEE_LAMPORTS: u64 = 100_000_000; // 0.1 SOL
@@ -72,4 +72,136 @@ pub enum RummikubError {
     MustPlayTileWithJoker,
     #[msg("Retrieved joker must be played in the same turn")]
     MustPlayRetrievedJoker,
+    #[msg("New games and joins are paused")]
+    GamePaused,
+    #[msg("Gameplay is paused")]
+    PlayPaused,
+    #[msg("Program is not paused")]
+    NotPaused,
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Refund account does not match seated player")]
+    InvalidRefundAccount,
+    #[msg("Game can only be closed after the prize is claimed or the game is cancelled")]
+    GameNotClosable,
+    #[msg("Insufficient bankroll balance")]
+    InsufficientBankroll,
+    #[msg("Game stakes are held in a different way (escrow vs bankroll)")]
+    StakeSourceMismatch,
+    #[msg("Bankroll account does not match seated player")]
+    InvalidBankrollAccount,
+    #[msg("Invalid amount")]
+    InvalidAmount,
+    #[msg("Validator is not on the allowlist")]
+    ValidatorNotAllowed,
+    #[msg("Too many validators")]
+    TooManyValidators,
+    #[msg("Only the player on move or a majority of players can undelegate a game in progress")]
+    UndelegateNotAllowed,
+    #[msg("Invalid commit policy")]
+    InvalidCommitPolicy,
+    #[msg("Invalid session duration")]
+    InvalidSessionDuration,
+    #[msg("Session key does not match this game or signer")]
+    InvalidSessionKey,
+    #[msg("Session key has expired")]
+    SessionExpired,
+    #[msg("Game is not delegated")]
+    GameNotDelegated,
+    #[msg("Recovery timeout has not elapsed since the last committed activity")]
+    RecoveryTimeoutNotReached,
+    #[msg("Escrow has already been settled or moved by a recovery")]
+    EscrowSettled,
+    #[msg("Invalid recovery timeout")]
+    InvalidRecoveryTimeout,
+    #[msg("Display name is too long")]
+    DisplayNameTooLong,
+    #[msg("Invalid player profile")]
+    InvalidProfile,
+    #[msg("Result already recorded for this player")]
+    ResultAlreadyRecorded,
+    #[msg("Rated games require a player profile")]
+    ProfileRequired,
+    #[msg("Invalid season")]
+    InvalidSeason,
+    #[msg("Game did not finish within the season")]
+    SeasonNotActive,
+    #[msg("Season has not ended yet")]
+    SeasonNotEnded,
+    #[msg("Season already finalized")]
+    SeasonFinalized,
+    #[msg("Payout shares must not exceed the leaderboard size or 100%")]
+    InvalidPayoutShares,
+    #[msg("The active season must be passed to route fees into it")]
+    SeasonRequired,
+    #[msg("Invalid tournament settings")]
+    InvalidTournament,
+    #[msg("Tournament registration is closed")]
+    RegistrationClosed,
+    #[msg("Tournament is full")]
+    TournamentFull,
+    #[msg("Player already registered")]
+    AlreadyRegistered,
+    #[msg("Current round has unreported tables")]
+    RoundInProgress,
+    #[msg("Invalid tournament table")]
+    InvalidTable,
+    #[msg("Table result already reported")]
+    TableAlreadyReported,
+    #[msg("Tournament is not finished")]
+    TournamentNotFinished,
+    #[msg("Tournament prizes already paid")]
+    PrizesAlreadyPaid,
+    #[msg("Match queue is full")]
+    QueueFull,
+    #[msg("Player is already queued")]
+    AlreadyQueued,
+    #[msg("Player is not queued")]
+    NotQueued,
+    #[msg("Not enough compatible players queued")]
+    NotEnoughPlayers,
+    #[msg("Lobby page is full")]
+    LobbyPageFull,
+    #[msg("Game is listed on a different lobby page")]
+    InvalidLobbyPage,
+    #[msg("Game ids from this range are assigned by the game counter")]
+    ReservedGameId,
+    #[msg("Too many invited players")]
+    TooManyInvites,
+    #[msg("Player is not invited to this game")]
+    NotInvited,
+    #[msg("Wrong or missing game password")]
+    InvalidPassword,
+    #[msg("Remaining seats are reserved")]
+    SeatsReserved,
+    #[msg("Record every profile result before a rematch")]
+    ResultNotRecorded,
+    #[msg("Invalid match target")]
+    InvalidMatchTarget,
+    #[msg("Team games need 4 players; partner melds need teams")]
+    InvalidTeamGame,
+    #[msg("Winner's partner account required")]
+    PartnerRequired,
+    #[msg("Game rules out of bounds")]
+    InvalidRules,
+    #[msg("Too many melds on the table")]
+    TooManyMelds,
+    #[msg("Meld has too many tiles")]
+    MeldTooLarge,
+    #[msg("Game account is already in the current layout")]
+    NothingToMigrate,
+    #[msg("Game account layout version is not supported by this program")]
+    UnsupportedGameVersion,
+    #[msg("Bankroll has stakes locked in a game")]
+    BankrollLocked,
+    #[msg("Tournament registration window has not passed")]
+    RegistrationStillOpen,
+    #[msg("The next round waits to be dealt from VRF randomness")]
+    RoundNotDealt,
+    #[msg("Escrow account required to migrate a game from the original layout")]
+    EscrowRequired,
+    #[msg("Tournament table game has not been reported yet")]
+    TableNotReported,
+    #[msg("Commit policy requires the magic program and context accounts")]
+    CommitAccountsRequired,
 }
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{GameStatus, RecoveryMode};
+
+/// A game reached `Finished`. When `undelegating` is set the game account is
+/// on its way back to base layer; `claim_prize` becomes possible once the
+/// account is owned by this program again.
+#[event]
+pub struct GameFinished {
+    pub game_id: u64,
+    pub winner: Pubkey,
+    pub winner_score: i16,
+    pub undelegating: bool,
+}
+
+/// A game was created under an id assigned by the game counter
+#[event]
+pub struct GameCreated {
+    pub game_id: u64,
+    pub authority: Pubkey,
+    pub max_players: u8,
+}
+
+/// A round of a match ended. The next one is not dealt yet: `awaiting_deal()`
+/// holds until the VRF callback deals it with `deal_round_with_randomness`
+#[event]
+pub struct RoundFinished {
+    pub game_id: u64,
+    pub round: u8,
+    pub round_winner: Pubkey,
+    pub match_scores: Vec<i16>, // Totals so far, in seat order
+}
+
+/// Rating changes of a rated game, in seat order. Applied to the profiles by
+/// `record_result`.
+#[event]
+pub struct RatingsUpdated {
+    pub game_id: u64,
+    pub players: Vec<Pubkey>,
+    pub ratings: Vec<u16>,
+    pub deltas: Vec<i16>,
+}
+
+/// Final result of a game, emitted right before its account is closed
+#[event]
+pub struct GameClosed {
+    pub game_id: u64,
+    pub authority: Pubkey,
+    pub status: GameStatus,
+    pub winner: Option<Pubkey>,
+    pub players: Vec<Pubkey>,
+    pub scores: Vec<i16>,
+    pub closed_at: i64,
+}
+
+/// A stalled game was restarted on base layer from its last committed
+/// snapshot under a new id; the entry fees moved to the new game's escrow.
+#[event]
+pub struct GameResumed {
+    pub game_id: u64,
+    pub new_game_id: u64,
+    pub turn_number: u32,
+}
+
+/// A stalled game was paid out on base layer from its last committed snapshot
+#[event]
+pub struct StalledGameSettled {
+    pub game_id: u64,
+    pub mode: RecoveryMode,
+    pub winner: Option<Pubkey>,
+    pub scores: Vec<i16>,
+}
@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::program::Rummikub;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Config::SPACE,
+        seeds = [CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, Rummikub>,
+    /// Only the upgrade authority may create the config
+    #[account(constraint = program_data.upgrade_authority_address == Some(admin.key()) @ RummikubError::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ RummikubError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+}
+
+pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.admin = ctx.accounts.admin.key();
+    config.paused = false;
+    config.play_paused = false;
+    config.bump = ctx.bumps.config;
+    config.allowed_validators = Vec::new();
+    config.recovery_timeout = DEFAULT_RECOVERY_TIMEOUT;
+    config.active_season = 0;
+    config.season_fee_bps = 0;
+
+    msg!("Config initialized. Admin: {}", config.admin);
+    Ok(())
+}
+
+pub fn set_pause(ctx: Context<UpdateConfig>, paused: bool, play_paused: bool) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.paused = paused;
+    config.play_paused = play_paused;
+
+    msg!("Pause updated. Games: {}, Play: {}", paused, play_paused);
+    Ok(())
+}
+
+pub fn set_validators(ctx: Context<UpdateConfig>, validators: Vec<Pubkey>) -> Result<()> {
+    require!(
+        validators.len() <= MAX_VALIDATORS,
+        RummikubError::TooManyValidators
+    );
+
+    let config = &mut ctx.accounts.config;
+    config.allowed_validators = validators;
+
+    msg!(
+        "Validator allowlist updated ({} entries)",
+        config.allowed_validators.len()
+    );
+    Ok(())
+}
+
+pub fn set_recovery_timeout(ctx: Context<UpdateConfig>, recovery_timeout: i64) -> Result<()> {
+    require!(recovery_timeout > 0, RummikubError::InvalidRecoveryTimeout);
+
+    let config = &mut ctx.accounts.config;
+    config.recovery_timeout = recovery_timeout;
+
+    msg!("Recovery timeout set to {}s", recovery_timeout);
+    Ok(())
+}
+
+pub fn set_season_fee(ctx: Context<UpdateConfig>, season_fee_bps: u16) -> Result<()> {
+    require!(season_fee_bps <= 10000, RummikubError::InvalidPayoutShares);
+
+    let config = &mut ctx.accounts.config;
+    config.season_fee_bps = season_fee_bps;
+
+    msg!(
+        "{} bps of each house fee now go to the active season",
+        season_fee_bps
+    );
+    Ok(())
+}
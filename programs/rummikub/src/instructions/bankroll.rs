@@ -0,0 +1,354 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct OpenBankroll<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Bankroll::SPACE,
+        seeds = [BANKROLL_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub bankroll: Account<'info, Bankroll>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Deposits and withdrawals move real SOL, so they only work while the
+/// bankroll is on base layer (a delegated bankroll is not owned by this
+/// program there and fails to deserialize).
+#[derive(Accounts)]
+pub struct FundBankroll<'info> {
+    #[account(
+        mut,
+        seeds = [BANKROLL_SEED, owner.key().as_ref()],
+        bump = bankroll.bump,
+        has_one = owner,
+    )]
+    pub bankroll: Account<'info, Bankroll>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct JoinGameWithBankroll<'info> {
+    #[account(mut)]
+    pub game: Account<'info, GameState>,
+    #[account(
+        mut,
+        seeds = [BANKROLL_SEED, player.key().as_ref()],
+        bump = bankroll.bump,
+        constraint = bankroll.owner == player.key() @ RummikubError::InvalidBankrollAccount,
+    )]
+    pub bankroll: Account<'info, Bankroll>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    pub player: Signer<'info>,
+    /// Attach to have this game counted in the player's lifetime statistics
+    #[account(seeds = [PROFILE_SEED, player.key().as_ref()], bump = profile.bump)]
+    pub profile: Option<Account<'info, PlayerProfile>>,
+}
+
+/// Remaining accounts: the bankrolls of every seated player, in seat order.
+#[derive(Accounts)]
+pub struct ClaimPrizeToBankroll<'info> {
+    #[account(
+        mut,
+        seeds = [GAME_SEED, &game.game_id.to_le_bytes()],
+        bump = game.bump,
+    )]
+    pub game: Account<'info, GameState>,
+    pub winner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SweepBankrollFees<'info> {
+    #[account(
+        mut,
+        seeds = [BANKROLL_SEED, bankroll.owner.as_ref()],
+        bump = bankroll.bump,
+    )]
+    pub bankroll: Account<'info, Bankroll>,
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump,
+    )]
+    /// CHECK: Treasury PDA to collect house fees
+    pub treasury: AccountInfo<'info>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    /// Required while a season fee is set: receives its share of the fees
+    #[account(
+        mut,
+        seeds = [SEASON_SEED, &config.active_season.to_le_bytes()],
+        bump = season.bump,
+    )]
+    pub season: Option<Account<'info, Season>>,
+}
+
+pub fn open_bankroll(ctx: Context<OpenBankroll>) -> Result<()> {
+    let bankroll = &mut ctx.accounts.bankroll;
+    bankroll.owner = ctx.accounts.owner.key();
+    bankroll.balance = 0;
+    bankroll.locked = 0;
+    bankroll.bump = ctx.bumps.bankroll;
+
+    msg!("Bankroll opened for {}", bankroll.owner);
+    Ok(())
+}
+
+pub fn deposit(ctx: Context<FundBankroll>, amount: u64) -> Result<()> {
+    require!(amount > 0, RummikubError::InvalidAmount);
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.owner.to_account_info(),
+                to: ctx.accounts.bankroll.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    let bankroll = &mut ctx.accounts.bankroll;
+    bankroll.balance += amount;
+
+    msg!(
+        "Deposited {} SOL. Bankroll balance: {} SOL",
+        amount as f64 / 1_000_000_000.0,
+        bankroll.balance as f64 / 1_000_000_000.0
+    );
+    Ok(())
+}
+
+pub fn withdraw(ctx: Context<FundBankroll>, amount: u64) -> Result<()> {
+    require!(amount > 0, RummikubError::InvalidAmount);
+
+    let bankroll = &mut ctx.accounts.bankroll;
+    require!(
+        bankroll.balance >= amount,
+        RummikubError::InsufficientBankroll
+    );
+
+    // Update balance BEFORE moving lamports
+    bankroll.balance -= amount;
+
+    **ctx
+        .accounts
+        .bankroll
+        .to_account_info()
+        .try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.owner.try_borrow_mut_lamports()? += amount;
+
+    msg!(
+        "Withdrew {} SOL. Bankroll balance: {} SOL",
+        amount as f64 / 1_000_000_000.0,
+        ctx.accounts.bankroll.balance as f64 / 1_000_000_000.0
+    );
+    Ok(())
+}
+
+pub fn join_game_with_bankroll(ctx: Context<JoinGameWithBankroll>) -> Result<()> {
+    require!(!ctx.accounts.config.paused, RummikubError::GamePaused);
+
+    let game = &mut ctx.accounts.game;
+    let bankroll = &mut ctx.accounts.bankroll;
+
+    // A game's pot is either fully escrowed or fully held in bankrolls
+    require!(
+        game.current_players == 0 || game.bankroll_funded,
+        RummikubError::StakeSourceMismatch
+    );
+
+    game.check_access(&ctx.accounts.player.key(), None)?;
+
+    let entry_fee = game.entry_fee;
+    require!(
+        bankroll.balance >= entry_fee,
+        RummikubError::InsufficientBankroll
+    );
+
+    // The stake stays in the bankroll account, locked until the game settles
+    bankroll.balance -= entry_fee;
+    bankroll.locked += entry_fee;
+
+    game.bankroll_funded = true;
+    let seat = game.add_player(ctx.accounts.player.key(), entry_fee)?;
+    game.seat_mut(seat).has_profile = ctx.accounts.profile.is_some();
+    if game.rated {
+        let profile = ctx
+            .accounts
+            .profile
+            .as_ref()
+            .ok_or(RummikubError::ProfileRequired)?;
+        game.seat_mut(seat).rating = profile.rating;
+    }
+    game.last_activity = Clock::get()?.unix_timestamp;
+    // The registry stays on base layer while this join may run inside the
+    // ER, so a listed game's entry is left for `prune_lobby` to refresh
+
+    msg!(
+        "Player {} joined game from bankroll. Entry fee: {} SOL",
+        ctx.accounts.player.key(),
+        entry_fee as f64 / 1_000_000_000.0
+    );
+    Ok(())
+}
+
+pub fn claim_prize_to_bankroll<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ClaimPrizeToBankroll<'info>>,
+) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+    let winner = ctx.accounts.winner.key();
+
+    require!(
+        game.game_status == GameStatus::Finished,
+        RummikubError::GameNotFinished
+    );
+    require!(game.winner == Some(winner), RummikubError::NotTheWinner);
+    require!(game.bankroll_funded, RummikubError::StakeSourceMismatch);
+    require!(game.prize_pool > 0, RummikubError::PrizeAlreadyClaimed);
+
+    let mut bankrolls = load_seat_bankrolls(game, ctx.remaining_accounts)?;
+    let winner_index = game.get_player_index(&winner)?;
+
+    let prize_pool = game.prize_pool;
+    let stake = prize_pool / game.current_players as u64;
+
+    // Set prize pool to 0 BEFORE crediting to prevent double claims
+    game.prize_pool = 0;
+
+    // Calculate house fee (5%)
+    let house_fee = (prize_pool as u128)
+        .checked_mul(HOUSE_FEE_BPS as u128)
+        .unwrap()
+        .checked_div(10000)
+        .unwrap() as u64;
+
+    let winner_prize = prize_pool.checked_sub(house_fee).unwrap();
+
+    // Team games split the prize between both partners
+    let partner_index = game.partner(winner_index);
+    let partner_share = partner_index.map_or(0, |_| winner_prize / 2);
+    let winner_prize = winner_prize - partner_share;
+
+    for (i, bankroll) in bankrolls.iter_mut().enumerate() {
+        bankroll.locked = bankroll
+            .locked
+            .checked_sub(stake)
+            .ok_or(RummikubError::InvalidBankrollAccount)?;
+        if i == winner_index {
+            bankroll.balance += winner_prize;
+        } else if Some(i) == partner_index {
+            bankroll.balance += partner_share;
+        }
+        bankroll.exit(&crate::ID)?;
+    }
+
+    // Move the losers' stakes into the winner's bankroll. The house fee is
+    // left there as surplus above `balance` until it is swept to the treasury.
+    let winner_bankroll = &ctx.remaining_accounts[winner_index];
+    for (i, account) in ctx.remaining_accounts.iter().enumerate() {
+        if i != winner_index && Some(i) != partner_index {
+            **account.try_borrow_mut_lamports()? -= stake;
+            **winner_bankroll.try_borrow_mut_lamports()? += stake;
+        }
+    }
+
+    // A partner keeps their own stake and is topped up to their share
+    if let Some(partner) = partner_index {
+        let top_up = partner_share - stake;
+        **winner_bankroll.try_borrow_mut_lamports()? -= top_up;
+        **ctx.remaining_accounts[partner].try_borrow_mut_lamports()? += top_up;
+    }
+
+    msg!(
+        "Prize credited to bankroll! Winner: {} SOL, House fee: {} SOL",
+        winner_prize as f64 / 1_000_000_000.0,
+        house_fee as f64 / 1_000_000_000.0
+    );
+    Ok(())
+}
+
+/// Bankroll prizes are credited inside the ER, where the season account
+/// can't be written, so the season's share of those house fees is split off
+/// here, once they reach base layer.
+pub fn sweep_bankroll_fees(ctx: Context<SweepBankrollFees>) -> Result<()> {
+    let bankroll = &ctx.accounts.bankroll;
+    let info = bankroll.to_account_info();
+
+    let rent = Rent::get()?.minimum_balance(info.data_len());
+    let owed = rent + bankroll.balance + bankroll.locked;
+    let surplus = info.lamports().saturating_sub(owed);
+
+    let config = &ctx.accounts.config;
+    let season_cut = if surplus > 0 && config.active_season != 0 && config.season_fee_bps > 0 {
+        let season = ctx
+            .accounts
+            .season
+            .as_mut()
+            .ok_or(RummikubError::SeasonRequired)?;
+        let cut = (surplus as u128)
+            .checked_mul(config.season_fee_bps as u128)
+            .unwrap()
+            .checked_div(10000)
+            .unwrap() as u64;
+        season.reward_pool += cut;
+        **info.try_borrow_mut_lamports()? -= cut;
+        **season.to_account_info().try_borrow_mut_lamports()? += cut;
+        cut
+    } else {
+        0
+    };
+
+    if surplus > 0 {
+        **info.try_borrow_mut_lamports()? -= surplus - season_cut;
+        **ctx.accounts.treasury.try_borrow_mut_lamports()? += surplus - season_cut;
+    }
+
+    msg!(
+        "Swept {} SOL of house fees to treasury (season pool: {} SOL)",
+        (surplus - season_cut) as f64 / 1_000_000_000.0,
+        season_cut as f64 / 1_000_000_000.0
+    );
+    Ok(())
+}
+
+/// Load and verify the bankroll of every seated player, in seat order.
+pub(crate) fn load_seat_bankrolls<'info>(
+    game: &GameState,
+    accounts: &'info [AccountInfo<'info>],
+) -> Result<Vec<Account<'info, Bankroll>>> {
+    require!(
+        accounts.len() == game.current_players as usize,
+        RummikubError::InvalidBankrollAccount
+    );
+
+    let mut bankrolls = Vec::with_capacity(accounts.len());
+    for (i, account) in accounts.iter().enumerate() {
+        let bankroll = Account::<Bankroll>::try_from(account)?;
+        let expected = Pubkey::create_program_address(
+            &[BANKROLL_SEED, bankroll.owner.as_ref(), &[bankroll.bump]],
+            &crate::ID,
+        )
+        .map_err(|_| RummikubError::InvalidBankrollAccount)?;
+
+        require!(
+            account.is_writable
+                && account.key() == expected
+                && bankroll.owner == game.seat(i).pubkey,
+            RummikubError::InvalidBankrollAccount
+        );
+        bankrolls.push(bankroll);
+    }
+    Ok(bankrolls)
+}
@@ -12,6 +12,14 @@ pub struct ClaimPrize<'info> {
         bump = game.bump,
     )]
     pub game: Account<'info, GameState>,
+    /// Entry fees live here; `game` must be back on base layer (owned by this
+    /// program) for this to deserialize, so the result has been committed
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, &game.game_id.to_le_bytes()],
+        bump = escrow.bump,
+    )]
+    pub escrow: Account<'info, Escrow>,
     #[account(mut)]
     pub winner: Signer<'info>,
     #[account(
@@ -21,6 +29,18 @@ pub struct ClaimPrize<'info> {
     )]
     /// CHECK: Treasury PDA to collect house fees
     pub treasury: AccountInfo<'info>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    /// Required while a season fee is set: receives its share of the house fee
+    #[account(
+        mut,
+        seeds = [SEASON_SEED, &config.active_season.to_le_bytes()],
+        bump = season.bump,
+    )]
+    pub season: Option<Account<'info, Season>>,
+    /// CHECK: Required in team games: the winner's partner, who gets half the prize
+    #[account(mut)]
+    pub partner: Option<AccountInfo<'info>>,
     pub system_program: Program<'info, System>,
 }
 
@@ -39,6 +59,10 @@ pub fn claim_prize(ctx: Context<ClaimPrize>) -> Result<()> {
 
     require!(game.prize_pool > 0, RummikubError::PrizeAlreadyClaimed);
 
+    // Bankroll-funded games pay out through claim_prize_to_bankroll
+    require!(!game.bankroll_funded, RummikubError::StakeSourceMismatch);
+    require!(!ctx.accounts.escrow.settled, RummikubError::EscrowSettled);
+
     let prize_pool = game.prize_pool;
 
     // Set prize pool to 0 BEFORE transferring to prevent reentrancy
@@ -53,26 +77,83 @@ pub fn claim_prize(ctx: Context<ClaimPrize>) -> Result<()> {
 
     let winner_prize = prize_pool.checked_sub(house_fee).unwrap();
 
-    // Transfer house fee to treasury
+    // Team games split the prize between both partners
+    let partner_share = match game.partner(game.get_player_index(&ctx.accounts.winner.key())?) {
+        Some(seat) => {
+            let partner = ctx
+                .accounts
+                .partner
+                .as_ref()
+                .ok_or(RummikubError::PartnerRequired)?;
+            require_keys_eq!(
+                partner.key(),
+                game.seat(seat).pubkey,
+                RummikubError::PartnerRequired
+            );
+            winner_prize / 2
+        }
+        None => 0,
+    };
+    let winner_prize = winner_prize - partner_share;
+
+    // Part of the house fee may go to the active season's reward pool
+    let config = &ctx.accounts.config;
+    let season_cut = if config.active_season != 0 && config.season_fee_bps > 0 {
+        let season = ctx
+            .accounts
+            .season
+            .as_mut()
+            .ok_or(RummikubError::SeasonRequired)?;
+        let cut = (house_fee as u128)
+            .checked_mul(config.season_fee_bps as u128)
+            .unwrap()
+            .checked_div(10000)
+            .unwrap() as u64;
+        season.reward_pool += cut;
+        **ctx
+            .accounts
+            .escrow
+            .to_account_info()
+            .try_borrow_mut_lamports()? -= cut;
+        **season.to_account_info().try_borrow_mut_lamports()? += cut;
+        cut
+    } else {
+        0
+    };
+
+    // Transfer the rest of the house fee to treasury
     **ctx
         .accounts
-        .game
+        .escrow
         .to_account_info()
-        .try_borrow_mut_lamports()? -= house_fee;
-    **ctx.accounts.treasury.try_borrow_mut_lamports()? += house_fee;
+        .try_borrow_mut_lamports()? -= house_fee - season_cut;
+    **ctx.accounts.treasury.try_borrow_mut_lamports()? += house_fee - season_cut;
 
     // Transfer winner prize to winner
     **ctx
         .accounts
-        .game
+        .escrow
         .to_account_info()
         .try_borrow_mut_lamports()? -= winner_prize;
     **ctx.accounts.winner.try_borrow_mut_lamports()? += winner_prize;
 
+    if let Some(partner) = &ctx.accounts.partner {
+        if partner_share > 0 {
+            **ctx
+                .accounts
+                .escrow
+                .to_account_info()
+                .try_borrow_mut_lamports()? -= partner_share;
+            **partner.try_borrow_mut_lamports()? += partner_share;
+        }
+    }
+
     msg!(
-        "Prize claimed! Winner: {} SOL, House fee: {} SOL",
+        "Prize claimed! Winner: {} SOL, Partner: {} SOL, House fee: {} SOL (season pool: {} SOL)",
         winner_prize as f64 / 1_000_000_000.0,
-        house_fee as f64 / 1_000_000_000.0
+        partner_share as f64 / 1_000_000_000.0,
+        house_fee as f64 / 1_000_000_000.0,
+        season_cut as f64 / 1_000_000_000.0
     );
 
     Ok(())
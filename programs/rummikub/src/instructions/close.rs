@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct CloseGame<'info> {
+    #[account(
+        mut,
+        seeds = [GAME_SEED, &game.game_id.to_le_bytes()],
+        bump = game.bump,
+        has_one = authority @ RummikubError::Unauthorized,
+        close = authority,
+    )]
+    pub game: Account<'info, GameState>,
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, &game.game_id.to_le_bytes()],
+        bump = escrow.bump,
+        close = authority,
+    )]
+    pub escrow: Account<'info, Escrow>,
+    /// Required for tournament table games, which stay open until reported
+    #[account(
+        seeds = [TOURNAMENT_SEED, &tournament.tournament_id.to_le_bytes()],
+        bump = tournament.bump,
+    )]
+    pub tournament: Option<Account<'info, Tournament>>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub fn close_game(ctx: Context<CloseGame>) -> Result<()> {
+    let game = &ctx.accounts.game;
+
+    // Only claimed or cancelled games can be closed, so no prize is left
+    // behind. A recovered escrow has already paid out or moved its funds.
+    let closable = ctx.accounts.escrow.settled
+        || match game.game_status {
+            GameStatus::Finished => game.prize_pool == 0,
+            GameStatus::Cancelled => true,
+            _ => false,
+        };
+    require!(closable, RummikubError::GameNotClosable);
+
+    // Profiles and ratings are updated from the game account, as `rematch`
+    // also requires
+    require!(
+        game.game_status != GameStatus::Finished
+            || game.seated().all(|p| !p.has_profile || p.result_recorded),
+        RummikubError::ResultNotRecorded
+    );
+
+    // `report_table` reads the game account, so a table game must outlive it
+    if let Some(key) = game.tournament {
+        let tournament = ctx
+            .accounts
+            .tournament
+            .as_ref()
+            .ok_or(RummikubError::InvalidTable)?;
+        require_keys_eq!(tournament.key(), key, RummikubError::InvalidTable);
+        require!(
+            tournament.table_reported(game.game_id),
+            RummikubError::TableNotReported
+        );
+    }
+
+    emit!(GameClosed {
+        game_id: game.game_id,
+        authority: game.authority,
+        status: game.game_status,
+        winner: game.winner,
+        players: game.seated().map(|p| p.pubkey).collect(),
+        scores: game.seated().map(|p| p.score).collect(),
+        closed_at: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Game {} and its escrow closed, rent returned to authority",
+        game.game_id
+    );
+    Ok(())
+}
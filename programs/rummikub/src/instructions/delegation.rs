@@ -4,8 +4,10 @@ use ephemeral_rollups_sdk::cpi::DelegateConfig;
 use ephemeral_rollups_sdk::ephem::{commit_accounts, commit_and_undelegate_accounts};
 
 use crate::constants::*;
+use crate::errors::*;
 use crate::state::*;
 
+/// Remaining accounts: the validator to delegate to (must be allowlisted).
 #[delegate]
 #[derive(Accounts)]
 pub struct DelegateGame<'info> {
@@ -13,28 +15,60 @@ pub struct DelegateGame<'info> {
     /// CHECK: The PDA to delegate
     #[account(mut, del)]
     pub game: AccountInfo<'info>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
 }
 
-pub fn delegate(ctx: Context<DelegateGame>) -> Result<()> {
-    // Load game state to get game_id for seeds
-    let game_data = ctx.accounts.game.try_borrow_data()?;
+pub fn delegate(
+    ctx: Context<DelegateGame>,
+    commit_policy: CommitPolicy,
+    commit_frequency_ms: u32,
+) -> Result<()> {
+    // Load game state to check who is delegating and get game_id for seeds
+    let mut game = {
+        let game_data = ctx.accounts.game.try_borrow_data()?;
+        GameState::try_deserialize(&mut &game_data[..])?
+    };
 
-    // Read game_id from account data (after 8-byte discriminator)
-    let game_id = u64::from_le_bytes(game_data[8..16].try_into().unwrap());
-    drop(game_data);
+    // Only the authority or a seated player may move the game
+    require!(
+        game.is_participant(&ctx.accounts.payer.key()),
+        RummikubError::Unauthorized
+    );
+    if let CommitPolicy::EveryNTurns { turns } = commit_policy {
+        require!(turns > 0, RummikubError::InvalidCommitPolicy);
+    }
+
+    let validator = allowed_validator(&ctx.accounts.config, ctx.remaining_accounts)?;
+
+    // Record the cadence; base layer is up to date as of this turn
+    game.commit_policy = commit_policy;
+    game.commit_frequency_ms = commit_frequency_ms;
+    game.last_committed_turn = game.turn_number;
+    {
+        let mut game_data = ctx.accounts.game.try_borrow_mut_data()?;
+        game.try_serialize(&mut &mut game_data[..])?;
+    }
+
+    let mut config = DelegateConfig {
+        validator: Some(validator),
+        ..Default::default()
+    };
+    if commit_frequency_ms > 0 {
+        config.commit_frequency_ms = commit_frequency_ms;
+    }
 
     ctx.accounts.delegate_game(
         &ctx.accounts.payer,
-        &[GAME_SEED, &game_id.to_le_bytes()],
-        DelegateConfig {
-            validator: ctx.remaining_accounts.first().map(|acc| acc.key()),
-            ..Default::default()
-        },
+        &[GAME_SEED, &game.game_id.to_le_bytes()],
+        config,
     )?;
     msg!("Game delegated to Ephemeral Rollup");
     Ok(())
 }
 
+/// Remaining accounts (undelegate only): extra seated players signing off on
+/// pulling a game back to base layer mid-play.
 #[commit]
 #[derive(Accounts)]
 pub struct CommitGame<'info> {
@@ -46,6 +80,12 @@ pub struct CommitGame<'info> {
 
 pub fn commit(ctx: Context<CommitGame>) -> Result<()> {
     let game = &mut ctx.accounts.game;
+    require!(
+        game.is_participant(&ctx.accounts.payer.key()),
+        RummikubError::Unauthorized
+    );
+
+    game.last_committed_turn = game.turn_number;
     game.exit(&crate::ID)?;
     commit_accounts(
         &ctx.accounts.payer,
@@ -59,6 +99,33 @@ pub fn commit(ctx: Context<CommitGame>) -> Result<()> {
 
 pub fn undelegate(ctx: Context<CommitGame>) -> Result<()> {
     let game = &mut ctx.accounts.game;
+    let payer = ctx.accounts.payer.key();
+
+    let signers: Vec<Pubkey> = std::iter::once(payer)
+        .chain(
+            ctx.remaining_accounts
+                .iter()
+                .filter(|acc| acc.is_signer)
+                .map(|acc| acc.key()),
+        )
+        .collect();
+
+    if !game.has_player_majority(&signers) {
+        if game.game_status == GameStatus::InProgress {
+            // Mid-game only the player on move may hand the game back, so
+            // nobody else's turn is interrupted
+            require!(
+                game.verify_turn(&payer).is_ok(),
+                RummikubError::UndelegateNotAllowed
+            );
+        } else {
+            require!(game.is_participant(&payer), RummikubError::Unauthorized);
+        }
+    }
+
+    // Base layer has nothing to commit to; `delegate` sets a new policy
+    game.last_committed_turn = game.turn_number;
+    game.commit_policy = CommitPolicy::Manual;
     game.exit(&crate::ID)?;
     commit_and_undelegate_accounts(
         &ctx.accounts.payer,
@@ -69,3 +136,87 @@ pub fn undelegate(ctx: Context<CommitGame>) -> Result<()> {
     msg!("Game state committed and undelegated");
     Ok(())
 }
+
+/// Remaining accounts: the validator to delegate to (must be allowlisted).
+#[delegate]
+#[derive(Accounts)]
+pub struct DelegateBankroll<'info> {
+    pub owner: Signer<'info>,
+    /// CHECK: The owner's bankroll PDA, checked by the delegation seeds
+    #[account(mut, del)]
+    pub bankroll: AccountInfo<'info>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+pub fn delegate_bankroll(ctx: Context<DelegateBankroll>) -> Result<()> {
+    let owner = ctx.accounts.owner.key();
+    let validator = allowed_validator(&ctx.accounts.config, ctx.remaining_accounts)?;
+
+    ctx.accounts.delegate_bankroll(
+        &ctx.accounts.owner,
+        &[BANKROLL_SEED, owner.as_ref()],
+        DelegateConfig {
+            validator: Some(validator),
+            ..Default::default()
+        },
+    )?;
+    msg!("Bankroll delegated to Ephemeral Rollup");
+    Ok(())
+}
+
+#[commit]
+#[derive(Accounts)]
+pub struct CommitBankroll<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [BANKROLL_SEED, owner.key().as_ref()],
+        bump = bankroll.bump,
+        has_one = owner,
+    )]
+    pub bankroll: Account<'info, Bankroll>,
+}
+
+pub fn commit_bankroll(ctx: Context<CommitBankroll>) -> Result<()> {
+    let bankroll = &mut ctx.accounts.bankroll;
+    require!(bankroll.locked == 0, RummikubError::BankrollLocked);
+    bankroll.exit(&crate::ID)?;
+    commit_accounts(
+        &ctx.accounts.owner,
+        vec![&ctx.accounts.bankroll.to_account_info()],
+        &ctx.accounts.magic_context,
+        &ctx.accounts.magic_program,
+    )?;
+    msg!("Bankroll balance settled to base layer");
+    Ok(())
+}
+
+pub fn undelegate_bankroll(ctx: Context<CommitBankroll>) -> Result<()> {
+    // Every seated bankroll must stay in the ER until the game settles
+    let bankroll = &mut ctx.accounts.bankroll;
+    require!(bankroll.locked == 0, RummikubError::BankrollLocked);
+    bankroll.exit(&crate::ID)?;
+    commit_and_undelegate_accounts(
+        &ctx.accounts.owner,
+        vec![&ctx.accounts.bankroll.to_account_info()],
+        &ctx.accounts.magic_context,
+        &ctx.accounts.magic_program,
+    )?;
+    msg!("Bankroll committed and undelegated");
+    Ok(())
+}
+
+/// The validator passed as the first remaining account, if the admin allows it.
+fn allowed_validator(config: &Config, remaining_accounts: &[AccountInfo]) -> Result<Pubkey> {
+    let validator = remaining_accounts
+        .first()
+        .map(|acc| acc.key())
+        .ok_or(RummikubError::ValidatorNotAllowed)?;
+    require!(
+        config.allowed_validators.contains(&validator),
+        RummikubError::ValidatorNotAllowed
+    );
+    Ok(validator)
+}
@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::instructions::bankroll::load_seat_bankrolls;
+use crate::instructions::lobby::unlist_game;
+use crate::state::*;
+
+/// Refund every seated player from a game caught by the pause.
+/// Remaining accounts: the seated players' wallets, in seat order, or their
+/// bankrolls for bankroll-funded games.
+#[derive(Accounts)]
+pub struct EmergencySettle<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [GAME_SEED, &game.game_id.to_le_bytes()],
+        bump = game.bump,
+    )]
+    pub game: Account<'info, GameState>,
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, &game.game_id.to_le_bytes()],
+        bump = escrow.bump,
+    )]
+    pub escrow: Account<'info, Escrow>,
+    /// Required when the game is listed in the open-game registry
+    #[account(
+        mut,
+        seeds = [LOBBY_SEED, &lobby.page.to_le_bytes()],
+        bump = lobby.bump,
+    )]
+    pub lobby: Option<Account<'info, LobbyPage>>,
+    pub caller: Signer<'info>,
+}
+
+pub fn emergency_settle<'info>(
+    ctx: Context<'_, '_, 'info, 'info, EmergencySettle<'info>>,
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let game = &mut ctx.accounts.game;
+    let caller = ctx.accounts.caller.key();
+
+    require!(config.paused, RummikubError::NotPaused);
+    require!(
+        caller == config.admin || game.get_player_index(&caller).is_ok(),
+        RummikubError::Unauthorized
+    );
+    require!(
+        game.game_status == GameStatus::WaitingForPlayers
+            || game.game_status == GameStatus::InProgress,
+        RummikubError::InvalidGameState
+    );
+    // Pausing joins alone leaves running games playable, so only the admin
+    // may void one then; otherwise a losing player could cancel their game
+    require!(
+        game.game_status == GameStatus::WaitingForPlayers
+            || config.play_paused
+            || caller == config.admin,
+        RummikubError::NotPaused
+    );
+
+    require!(!ctx.accounts.escrow.settled, RummikubError::EscrowSettled);
+    // A cancelled table could never be reported; stalled tables are
+    // forfeited through the tournament instead
+    require!(game.tournament.is_none(), RummikubError::InvalidTable);
+
+    let seated = game.current_players as usize;
+    require!(
+        ctx.remaining_accounts.len() == seated,
+        RummikubError::InvalidRefundAccount
+    );
+
+    // Mark the game cancelled BEFORE moving lamports
+    let prize_pool = game.prize_pool;
+    game.prize_pool = 0;
+    game.game_status = GameStatus::Cancelled;
+    unlist_game(game, ctx.accounts.lobby.as_deref_mut())?;
+
+    if seated > 0 && game.bankroll_funded {
+        // Stakes never left the bankrolls; just unlock them
+        let refund = prize_pool / seated as u64;
+        let mut bankrolls = load_seat_bankrolls(game, ctx.remaining_accounts)?;
+        for bankroll in bankrolls.iter_mut() {
+            bankroll.locked = bankroll
+                .locked
+                .checked_sub(refund)
+                .ok_or(RummikubError::InvalidBankrollAccount)?;
+            bankroll.balance += refund;
+            bankroll.exit(&crate::ID)?;
+        }
+
+        msg!(
+            "Emergency settle: returned {} SOL to each of {} bankrolls",
+            refund as f64 / 1_000_000_000.0,
+            seated
+        );
+    } else if seated > 0 {
+        let refund = prize_pool / seated as u64;
+        for (i, account) in ctx.remaining_accounts.iter().enumerate() {
+            require!(
+                account.key() == game.seat(i).pubkey && account.is_writable,
+                RummikubError::InvalidRefundAccount
+            );
+
+            **ctx
+                .accounts
+                .escrow
+                .to_account_info()
+                .try_borrow_mut_lamports()? -= refund;
+            **account.try_borrow_mut_lamports()? += refund;
+        }
+
+        msg!(
+            "Emergency settle: refunded {} SOL to each of {} players",
+            refund as f64 / 1_000_000_000.0,
+            seated
+        );
+    }
+
+    msg!("Game {} cancelled", game.game_id);
+    Ok(())
+}
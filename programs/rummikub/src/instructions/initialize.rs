@@ -2,44 +2,202 @@ use anchor_lang::prelude::*;
 
 use crate::constants::*;
 use crate::errors::*;
+use crate::events::GameCreated;
+use crate::instructions::lobby::list_game;
 use crate::state::*;
 
 #[derive(Accounts)]
-#[instruction(game_id: u64)]
+#[instruction(game_id: u64, max_players: u8)]
 pub struct InitializeGame<'info> {
     #[account(
         init,
         payer = authority,
-        space = 8 + GameState::SPACE,
+        space = 8 + GameState::space(max_players),
         seeds = [GAME_SEED, &game_id.to_le_bytes()],
         bump
     )]
     pub game: Account<'info, GameState>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Escrow::SPACE,
+        seeds = [ESCROW_SEED, &game_id.to_le_bytes()],
+        bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    /// Attach to list the game in the open-game registry
+    #[account(
+        mut,
+        seeds = [LOBBY_SEED, &lobby.page.to_le_bytes()],
+        bump = lobby.bump,
+    )]
+    pub lobby: Option<Account<'info, LobbyPage>>,
     #[account(mut)]
     pub authority: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn initialize_game(ctx: Context<InitializeGame>, game_id: u64, max_players: u8) -> Result<()> {
+#[derive(Accounts)]
+pub struct InitializeGameCounter<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + GameCounter::SPACE,
+        seeds = [COUNTER_SEED],
+        bump
+    )]
+    pub counter: Account<'info, GameCounter>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Same as `InitializeGame`, with the id taken from the game counter.
+#[derive(Accounts)]
+#[instruction(max_players: u8)]
+pub struct InitializeNextGame<'info> {
+    #[account(
+        mut,
+        seeds = [COUNTER_SEED],
+        bump = counter.bump,
+    )]
+    pub counter: Account<'info, GameCounter>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + GameState::space(max_players),
+        seeds = [GAME_SEED, &counter.next_game_id.to_le_bytes()],
+        bump
+    )]
+    pub game: Account<'info, GameState>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Escrow::SPACE,
+        seeds = [ESCROW_SEED, &counter.next_game_id.to_le_bytes()],
+        bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    /// Attach to list the game in the open-game registry
+    #[account(
+        mut,
+        seeds = [LOBBY_SEED, &lobby.page.to_le_bytes()],
+        bump = lobby.bump,
+    )]
+    pub lobby: Option<Account<'info, LobbyPage>>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_game(
+    ctx: Context<InitializeGame>,
+    game_id: u64,
+    max_players: u8,
+    options: GameOptions,
+    access: Option<GameAccess>,
+) -> Result<()> {
+    require!(!ctx.accounts.config.paused, RummikubError::GamePaused);
     require!(
-        max_players >= MIN_PLAYERS as u8 && max_players <= MAX_PLAYERS as u8,
-        RummikubError::InvalidPlayerCount
+        game_id < FIRST_COUNTER_GAME_ID,
+        RummikubError::ReservedGameId
     );
 
+    let escrow = &mut ctx.accounts.escrow;
+    escrow.game_id = game_id;
+    escrow.bump = ctx.bumps.escrow;
+    escrow.settled = false;
+
     let game = &mut ctx.accounts.game;
-    game.game_id = game_id;
-    game.authority = ctx.accounts.authority.key();
-    game.max_players = max_players;
-    game.current_players = 0;
-    game.current_turn = 0;
-    game.game_status = GameStatus::WaitingForPlayers;
-    game.winner = None;
-    game.prize_pool = 0;
-    game.bump = ctx.bumps.game;
-
-    // Initialize tile pool with all tiles
-    game.initialize_tile_pool()?;
-
-    msg!("Game {} initialized for {} players", game_id, max_players);
+    open_game(
+        game,
+        ctx.accounts.authority.key(),
+        game_id,
+        max_players,
+        options,
+        access.unwrap_or_default(),
+        ctx.bumps.game,
+    )?;
+    if let Some(lobby) = ctx.accounts.lobby.as_mut() {
+        list_game(game, lobby)?;
+    }
+    Ok(())
+}
+
+pub fn initialize_game_counter(ctx: Context<InitializeGameCounter>) -> Result<()> {
+    let counter = &mut ctx.accounts.counter;
+    counter.next_game_id = FIRST_COUNTER_GAME_ID;
+    counter.bump = ctx.bumps.counter;
+
+    msg!("Game counter initialized");
+    Ok(())
+}
+
+pub fn initialize_next_game(
+    ctx: Context<InitializeNextGame>,
+    max_players: u8,
+    options: GameOptions,
+    access: Option<GameAccess>,
+) -> Result<u64> {
+    require!(!ctx.accounts.config.paused, RummikubError::GamePaused);
+
+    let counter = &mut ctx.accounts.counter;
+    let game_id = counter.next_game_id;
+    counter.next_game_id += 1;
+
+    let escrow = &mut ctx.accounts.escrow;
+    escrow.game_id = game_id;
+    escrow.bump = ctx.bumps.escrow;
+    escrow.settled = false;
+
+    let game = &mut ctx.accounts.game;
+    open_game(
+        game,
+        ctx.accounts.authority.key(),
+        game_id,
+        max_players,
+        options,
+        access.unwrap_or_default(),
+        ctx.bumps.game,
+    )?;
+    if let Some(lobby) = ctx.accounts.lobby.as_mut() {
+        list_game(game, lobby)?;
+    }
+
+    emit!(GameCreated {
+        game_id,
+        authority: ctx.accounts.authority.key(),
+        max_players,
+    });
+    Ok(game_id)
+}
+
+/// Set up a freshly created game with its access restrictions.
+fn open_game(
+    game: &mut GameState,
+    authority: Pubkey,
+    game_id: u64,
+    max_players: u8,
+    options: GameOptions,
+    access: GameAccess,
+    bump: u8,
+) -> Result<()> {
+    access.validate(max_players)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    game.setup(game_id, authority, max_players, options, bump, now)?;
+    game.access = access;
+
+    msg!(
+        "Game {} initialized for {} players (rated: {}, private: {})",
+        game_id,
+        max_players,
+        options.rated,
+        game.access.is_private()
+    );
     Ok(())
 }
@@ -3,36 +3,93 @@ use anchor_lang::system_program;
 
 use crate::constants::*;
 use crate::errors::*;
+use crate::instructions::lobby::sync_lobby;
 use crate::state::*;
 
 #[derive(Accounts)]
 pub struct JoinGame<'info> {
     #[account(mut)]
     pub game: Account<'info, GameState>,
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, &game.game_id.to_le_bytes()],
+        bump = escrow.bump,
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
     #[account(mut)]
     pub player: Signer<'info>,
+    /// Attach to have this game counted in the player's lifetime statistics
+    #[account(seeds = [PROFILE_SEED, player.key().as_ref()], bump = profile.bump)]
+    pub profile: Option<Account<'info, PlayerProfile>>,
+    /// Required when the game is listed in the open-game registry
+    #[account(
+        mut,
+        seeds = [LOBBY_SEED, &lobby.page.to_le_bytes()],
+        bump = lobby.bump,
+    )]
+    pub lobby: Option<Account<'info, LobbyPage>>,
     pub system_program: Program<'info, System>,
 }
 
+/// The authority removes a player before the game starts; their stake is
+/// refunded from the escrow.
+#[derive(Accounts)]
+pub struct KickPlayer<'info> {
+    #[account(
+        mut,
+        seeds = [GAME_SEED, &game.game_id.to_le_bytes()],
+        bump = game.bump,
+        has_one = authority @ RummikubError::Unauthorized,
+    )]
+    pub game: Account<'info, GameState>,
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, &game.game_id.to_le_bytes()],
+        bump = escrow.bump,
+    )]
+    pub escrow: Account<'info, Escrow>,
+    pub authority: Signer<'info>,
+    /// CHECK: Wallet of the kicked player, matched against the seated players
+    #[account(mut)]
+    pub player: AccountInfo<'info>,
+    /// Required when the game is listed in the open-game registry
+    #[account(
+        mut,
+        seeds = [LOBBY_SEED, &lobby.page.to_le_bytes()],
+        bump = lobby.bump,
+    )]
+    pub lobby: Option<Account<'info, LobbyPage>>,
+}
+
 pub fn join_game(ctx: Context<JoinGame>) -> Result<()> {
+    join(ctx, None)
+}
+
+pub fn join_game_with_password(ctx: Context<JoinGame>, password: Vec<u8>) -> Result<()> {
+    join(ctx, Some(&password))
+}
+
+fn join(ctx: Context<JoinGame>, password: Option<&[u8]>) -> Result<()> {
+    require!(!ctx.accounts.config.paused, RummikubError::GamePaused);
     require!(
-        ctx.accounts.game.game_status == GameStatus::WaitingForPlayers,
-        RummikubError::GameAlreadyStarted
-    );
-    require!(
-        ctx.accounts.game.current_players < ctx.accounts.game.max_players,
-        RummikubError::GameFull
+        !ctx.accounts.game.bankroll_funded,
+        RummikubError::StakeSourceMismatch
     );
+    ctx.accounts
+        .game
+        .check_access(&ctx.accounts.player.key(), password)?;
 
-    // Transfer 0.1 SOL entry fee to game account
-    let entry_fee = ENTRY_FEE_LAMPORTS;
+    // Transfer the entry fee to the game's escrow
+    let entry_fee = ctx.accounts.game.entry_fee;
 
     system_program::transfer(
         CpiContext::new(
             ctx.accounts.system_program.to_account_info(),
             system_program::Transfer {
                 from: ctx.accounts.player.to_account_info(),
-                to: ctx.accounts.game.to_account_info(),
+                to: ctx.accounts.escrow.to_account_info(),
             },
         ),
         entry_fee,
@@ -40,33 +97,19 @@ pub fn join_game(ctx: Context<JoinGame>) -> Result<()> {
 
     // Now get mutable reference after the transfer
     let game = &mut ctx.accounts.game;
-
-    // Add entry fee to prize pool
-    game.prize_pool += entry_fee;
-
-    let player_index = game.current_players as usize;
-    game.players[player_index] = Player {
-        pubkey: ctx.accounts.player.key(),
-        tiles: [Tile::default(); 21],
-        tile_count: 0,
-        has_opened: false,
-        score: 0,
-    };
-
-    game.current_players += 1;
-
-    // Draw initial tiles for the player
-    game.draw_initial_tiles(player_index)?;
-
-    // Start game if all players joined
-    if game.current_players == game.max_players {
-        game.game_status = GameStatus::InProgress;
-        msg!(
-            "Game started with {} players. Prize pool: {} lamports",
-            game.current_players,
-            game.prize_pool
-        );
+    let seat = game.add_player(ctx.accounts.player.key(), entry_fee)?;
+    game.seat_mut(seat).has_profile = ctx.accounts.profile.is_some();
+    if game.rated {
+        let profile = ctx
+            .accounts
+            .profile
+            .as_ref()
+            .ok_or(RummikubError::ProfileRequired)?;
+        game.seat_mut(seat).rating = profile.rating;
     }
+    game.last_activity = Clock::get()?.unix_timestamp;
+
+    sync_lobby(game, ctx.accounts.lobby.as_deref_mut())?;
 
     msg!(
         "Player {} joined game. Entry fee: {} SOL",
@@ -75,3 +118,33 @@ pub fn join_game(ctx: Context<JoinGame>) -> Result<()> {
     );
     Ok(())
 }
+
+pub fn kick_player(ctx: Context<KickPlayer>) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+    let player = ctx.accounts.player.key();
+
+    // Bankroll stakes are locked in the bankrolls, which live in the ER
+    require!(!game.bankroll_funded, RummikubError::StakeSourceMismatch);
+    let index = game.get_player_index(&player)?;
+
+    // Unseat the player BEFORE moving lamports
+    let refund = game.remove_player(index)?;
+    game.access.reserved.retain(|r| *r != player);
+
+    sync_lobby(game, ctx.accounts.lobby.as_deref_mut())?;
+
+    **ctx
+        .accounts
+        .escrow
+        .to_account_info()
+        .try_borrow_mut_lamports()? -= refund;
+    **ctx.accounts.player.try_borrow_mut_lamports()? += refund;
+
+    msg!(
+        "Player {} kicked from game {}. Refunded {} SOL",
+        player,
+        game.game_id,
+        refund as f64 / 1_000_000_000.0
+    );
+    Ok(())
+}
@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::state::*;
+
+/// Anyone can open the next registry page once the earlier ones fill up.
+#[derive(Accounts)]
+#[instruction(page: u32)]
+pub struct OpenLobbyPage<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + LobbyPage::SPACE,
+        seeds = [LOBBY_SEED, &page.to_le_bytes()],
+        bump
+    )]
+    pub lobby: Account<'info, LobbyPage>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless. Remaining accounts: the game accounts of `game_ids`, in the
+/// same order; their entries may be stale (filled inside the ER, cancelled or
+/// closed).
+#[derive(Accounts)]
+pub struct PruneLobby<'info> {
+    #[account(
+        mut,
+        seeds = [LOBBY_SEED, &lobby.page.to_le_bytes()],
+        bump = lobby.bump,
+    )]
+    pub lobby: Account<'info, LobbyPage>,
+}
+
+pub fn open_lobby_page(ctx: Context<OpenLobbyPage>, page: u32) -> Result<()> {
+    let lobby = &mut ctx.accounts.lobby;
+    lobby.page = page;
+    lobby.bump = ctx.bumps.lobby;
+    lobby.entries = Vec::new();
+
+    msg!("Lobby page {} opened", page);
+    Ok(())
+}
+
+pub fn prune_lobby(ctx: Context<PruneLobby>, game_ids: Vec<u64>) -> Result<()> {
+    let lobby = &mut ctx.accounts.lobby;
+    let before = lobby.entries.len();
+
+    require!(
+        game_ids.len() == ctx.remaining_accounts.len(),
+        RummikubError::InvalidGameState
+    );
+
+    for (&game_id, info) in game_ids.iter().zip(ctx.remaining_accounts.iter()) {
+        let (expected, _) =
+            Pubkey::find_program_address(&[GAME_SEED, &game_id.to_le_bytes()], &crate::ID);
+        require_keys_eq!(info.key(), expected, RummikubError::InvalidGameState);
+
+        // A closed game has no data left. A delegated game is owned by the
+        // delegation program on base layer but still holds its last commit.
+        let game = if info.data_is_empty() {
+            None
+        } else {
+            let game_data = info.try_borrow_data()?;
+            GameState::try_deserialize_unchecked(&mut &game_data[..]).ok()
+        };
+
+        match game {
+            Some(game) if game.game_status == GameStatus::WaitingForPlayers => {
+                lobby.update(game_id, game.max_players - game.current_players)
+            }
+            _ => lobby.remove(game_id),
+        }
+    }
+
+    msg!(
+        "Lobby page {}: pruned {} entries",
+        lobby.page,
+        before - lobby.entries.len()
+    );
+    Ok(())
+}
+
+/// Add a freshly created game to an open-game registry page.
+pub fn list_game(game: &mut GameState, lobby: &mut LobbyPage) -> Result<()> {
+    lobby.list(LobbyEntry {
+        game_id: game.game_id,
+        stake: game.entry_fee,
+        seats_left: game.max_players,
+        options: GameOptions {
+            rated: game.rated,
+            match_target: game.match_target,
+            teams: game.teams,
+            partner_melds: game.partner_melds,
+            rules: game.rules,
+        },
+        private: game.access.is_private(),
+        created_at: game.last_activity,
+    })?;
+    game.lobby_page = Some(lobby.page);
+    Ok(())
+}
+
+/// Bring a listed game's registry entry in line with its seats and status:
+/// open seats are refreshed while it fills, anything else drops it. The
+/// lobby account is required only when the game is listed.
+pub fn sync_lobby(game: &GameState, lobby: Option<&mut LobbyPage>) -> Result<()> {
+    if game.game_status != GameStatus::WaitingForPlayers {
+        return unlist_game(game, lobby);
+    }
+    if let Some(lobby) = listing_page(game, lobby)? {
+        lobby.update(game.game_id, game.max_players - game.current_players);
+    }
+    Ok(())
+}
+
+/// Drop a game's registry entry, if it is listed.
+pub fn unlist_game(game: &GameState, lobby: Option<&mut LobbyPage>) -> Result<()> {
+    if let Some(lobby) = listing_page(game, lobby)? {
+        lobby.remove(game.game_id);
+    }
+    Ok(())
+}
+
+/// The page listing the game, checked against the one passed in
+fn listing_page<'a>(
+    game: &GameState,
+    lobby: Option<&'a mut LobbyPage>,
+) -> Result<Option<&'a mut LobbyPage>> {
+    match game.lobby_page {
+        Some(page) => {
+            let lobby = lobby.ok_or(RummikubError::InvalidLobbyPage)?;
+            require!(lobby.page == page, RummikubError::InvalidLobbyPage);
+            Ok(Some(lobby))
+        }
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,233 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(stake: u64, player_count: u8)]
+pub struct OpenQueue<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ RummikubError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + MatchQueue::SPACE,
+        seeds = [QUEUE_SEED, &stake.to_le_bytes(), &[player_count]],
+        bump
+    )]
+    pub queue: Account<'info, MatchQueue>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Enqueue<'info> {
+    #[account(
+        mut,
+        seeds = [QUEUE_SEED, &queue.stake.to_le_bytes(), &[queue.player_count]],
+        bump = queue.bump,
+    )]
+    pub queue: Account<'info, MatchQueue>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub player: Signer<'info>,
+    /// Attach to be matched by rating and have the game counted in the profile
+    #[account(seeds = [PROFILE_SEED, player.key().as_ref()], bump = profile.bump)]
+    pub profile: Option<Account<'info, PlayerProfile>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct LeaveQueue<'info> {
+    #[account(
+        mut,
+        seeds = [QUEUE_SEED, &queue.stake.to_le_bytes(), &[queue.player_count]],
+        bump = queue.bump,
+    )]
+    pub queue: Account<'info, MatchQueue>,
+    #[account(mut)]
+    pub player: Signer<'info>,
+}
+
+/// Permissionless crank. The caller pays the new game's rent and becomes its
+/// authority, so they can reclaim it with `close_game` afterwards.
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct MatchPlayers<'info> {
+    #[account(
+        mut,
+        seeds = [QUEUE_SEED, &queue.stake.to_le_bytes(), &[queue.player_count]],
+        bump = queue.bump,
+    )]
+    pub queue: Account<'info, MatchQueue>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = cranker,
+        space = 8 + GameState::space(queue.player_count),
+        seeds = [GAME_SEED, &game_id.to_le_bytes()],
+        bump
+    )]
+    pub game: Account<'info, GameState>,
+    #[account(
+        init,
+        payer = cranker,
+        space = 8 + Escrow::SPACE,
+        seeds = [ESCROW_SEED, &game_id.to_le_bytes()],
+        bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn open_queue(
+    ctx: Context<OpenQueue>,
+    stake: u64,
+    player_count: u8,
+    rating_band: u16,
+) -> Result<()> {
+    require!(
+        player_count >= MIN_PLAYERS as u8 && player_count <= SIX_PLAYER_SEATS as u8,
+        RummikubError::InvalidPlayerCount
+    );
+
+    let queue = &mut ctx.accounts.queue;
+    queue.stake = stake;
+    queue.player_count = player_count;
+    queue.rating_band = rating_band;
+    queue.bump = ctx.bumps.queue;
+    queue.waiting = Vec::new();
+
+    msg!(
+        "Queue opened: {} SOL, {} players, rating band {}",
+        stake as f64 / 1_000_000_000.0,
+        player_count,
+        rating_band
+    );
+    Ok(())
+}
+
+pub fn enqueue(ctx: Context<Enqueue>) -> Result<()> {
+    require!(!ctx.accounts.config.paused, RummikubError::GamePaused);
+
+    let player = ctx.accounts.player.key();
+    let queue = &ctx.accounts.queue;
+    require!(
+        queue.waiting.len() < MAX_QUEUE_SIZE,
+        RummikubError::QueueFull
+    );
+    require!(
+        !queue.waiting.iter().any(|e| e.player == player),
+        RummikubError::AlreadyQueued
+    );
+
+    // The stake waits in the queue account until a table is formed
+    let stake = queue.stake;
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.player.to_account_info(),
+                to: ctx.accounts.queue.to_account_info(),
+            },
+        ),
+        stake,
+    )?;
+
+    let profile = &ctx.accounts.profile;
+    ctx.accounts.queue.waiting.push(QueueEntry {
+        player,
+        rating: profile.as_ref().map_or(INITIAL_RATING, |p| p.rating),
+        has_profile: profile.is_some(),
+    });
+
+    msg!(
+        "Player {} queued ({} waiting)",
+        player,
+        ctx.accounts.queue.waiting.len()
+    );
+    Ok(())
+}
+
+pub fn leave_queue(ctx: Context<LeaveQueue>) -> Result<()> {
+    let player = ctx.accounts.player.key();
+    let queue = &mut ctx.accounts.queue;
+
+    let position = queue
+        .waiting
+        .iter()
+        .position(|e| e.player == player)
+        .ok_or(RummikubError::NotQueued)?;
+
+    // Remove the entry BEFORE refunding
+    queue.waiting.remove(position);
+    let stake = queue.stake;
+
+    **queue.to_account_info().try_borrow_mut_lamports()? -= stake;
+    **ctx.accounts.player.try_borrow_mut_lamports()? += stake;
+
+    msg!("Player {} left the queue", player);
+    Ok(())
+}
+
+pub fn match_players(ctx: Context<MatchPlayers>, game_id: u64) -> Result<()> {
+    require!(!ctx.accounts.config.paused, RummikubError::GamePaused);
+    require!(
+        game_id < FIRST_COUNTER_GAME_ID,
+        RummikubError::ReservedGameId
+    );
+
+    let queue = &mut ctx.accounts.queue;
+    let group = queue.pick_group().ok_or(RummikubError::NotEnoughPlayers)?;
+    let seated: Vec<QueueEntry> = group.iter().map(|&i| queue.waiting[i]).collect();
+
+    // Take the players off the queue (highest position first keeps indices valid)
+    for &i in group.iter().rev() {
+        queue.waiting.remove(i);
+    }
+
+    let stake = queue.stake;
+    let total = stake * seated.len() as u64;
+    **queue.to_account_info().try_borrow_mut_lamports()? -= total;
+    **ctx
+        .accounts
+        .escrow
+        .to_account_info()
+        .try_borrow_mut_lamports()? += total;
+
+    let escrow = &mut ctx.accounts.escrow;
+    escrow.game_id = game_id;
+    escrow.bump = ctx.bumps.escrow;
+    escrow.settled = false;
+
+    let game = &mut ctx.accounts.game;
+    game.setup(
+        game_id,
+        ctx.accounts.cranker.key(),
+        seated.len() as u8,
+        GameOptions::for_players(seated.len() as u8),
+        ctx.bumps.game,
+        Clock::get()?.unix_timestamp,
+    )?;
+    game.entry_fee = stake;
+
+    // Seating the last player starts the game
+    for entry in &seated {
+        let seat = game.add_player(entry.player, stake)?;
+        game.seat_mut(seat).has_profile = entry.has_profile;
+    }
+
+    msg!("Matched {} players into game {}", seated.len(), game_id);
+    Ok(())
+}
@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::legacy::{game_version, load_game};
+use crate::state::*;
+
+/// Upgrade a game stored in an earlier layout to the current one, in place.
+/// Anyone may call it; games must be on base layer (a delegated game is not
+/// owned by this program there).
+#[derive(Accounts)]
+pub struct MigrateGame<'info> {
+    /// CHECK: Game account in any layout. Its discriminator and PDA are
+    /// verified in `migrate_game`
+    #[account(mut, owner = crate::ID)]
+    pub game: AccountInfo<'info>,
+    /// CHECK: Required for v0 games, which held entry fees in the game
+    /// account itself: created here at `[ESCROW_SEED, game_id]` and given the
+    /// unclaimed prize pool. Verified in `migrate_game`
+    #[account(mut)]
+    pub escrow: Option<AccountInfo<'info>>,
+    /// Covers extra rent if the migrated account is larger
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_game(ctx: Context<MigrateGame>) -> Result<()> {
+    let info = ctx.accounts.game.to_account_info();
+
+    let (from, game) = {
+        let data = info.try_borrow_data()?;
+        let from = game_version(&data);
+        require!(from != Some(GAME_VERSION), RummikubError::NothingToMigrate);
+        (from.unwrap_or_default(), load_game(&data)?)
+    };
+    let expected = Pubkey::create_program_address(
+        &[GAME_SEED, &game.game_id.to_le_bytes(), &[game.bump]],
+        &crate::ID,
+    )
+    .map_err(|_| RummikubError::InvalidGameState)?;
+    require_keys_eq!(info.key(), expected, RummikubError::InvalidGameState);
+
+    if from == 0 {
+        let escrow = ctx
+            .accounts
+            .escrow
+            .as_ref()
+            .ok_or(RummikubError::EscrowRequired)?;
+        create_escrow(
+            &ctx.accounts.payer,
+            escrow,
+            &ctx.accounts.system_program,
+            game.game_id,
+        )?;
+
+        // The pool leaves the game account, which keeps its rent
+        **info.try_borrow_mut_lamports()? -= game.prize_pool;
+        **escrow.try_borrow_mut_lamports()? += game.prize_pool;
+    }
+
+    let old_len = info.data_len();
+    let new_len = 8 + GameState::space(game.max_players);
+
+    let rent = Rent::get()?.minimum_balance(new_len);
+    let top_up = rent.saturating_sub(info.lamports());
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: info.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+
+    // Surplus rent from a shrink stays in the account and is returned to the
+    // authority by `close_game`. Growing is capped at 10 KiB per call, far
+    // more than any layout change adds
+    info.resize(new_len)?;
+    game.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+    msg!(
+        "Game {} migrated from v{} to v{}: {} -> {} bytes",
+        game.game_id,
+        from,
+        GAME_VERSION,
+        old_len,
+        new_len
+    );
+    Ok(())
+}
+
+fn create_escrow<'info>(
+    payer: &Signer<'info>,
+    escrow: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    game_id: u64,
+) -> Result<()> {
+    let id = game_id.to_le_bytes();
+    let (expected, bump) = Pubkey::find_program_address(&[ESCROW_SEED, &id], &crate::ID);
+    require_keys_eq!(escrow.key(), expected, RummikubError::InvalidGameState);
+
+    let space = 8 + Escrow::SPACE;
+    system_program::create_account(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            system_program::CreateAccount {
+                from: payer.to_account_info(),
+                to: escrow.clone(),
+            },
+            &[&[ESCROW_SEED, &id, &[bump]]],
+        ),
+        Rent::get()?.minimum_balance(space),
+        space as u64,
+        &crate::ID,
+    )?;
+
+    let state = Escrow {
+        game_id,
+        bump,
+        settled: false,
+    };
+    state.try_serialize(&mut &mut escrow.try_borrow_mut_data()?[..])
+}
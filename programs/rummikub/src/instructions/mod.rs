@@ -1,13 +1,39 @@
+pub mod admin;
+pub mod bankroll;
 pub mod claim;
+pub mod close;
 pub mod delegation;
+pub mod emergency;
 pub mod initialize;
 pub mod join;
+pub mod lobby;
+pub mod matchmaking;
+pub mod migrate;
 pub mod play;
+pub mod profile;
+pub mod recovery;
+pub mod rematch;
+pub mod season;
+pub mod session;
+pub mod tournament;
 pub mod vrf_shuffle;
 
+pub use admin::*;
+pub use bankroll::*;
 pub use claim::*;
+pub use close::*;
 pub use delegation::*;
+pub use emergency::*;
 pub use initialize::*;
 pub use join::*;
+pub use lobby::*;
+pub use matchmaking::*;
+pub use migrate::*;
 pub use play::*;
+pub use profile::*;
+pub use recovery::*;
+pub use rematch::*;
+pub use season::*;
+pub use session::*;
+pub use tournament::*;
 pub use vrf_shuffle::*;
@@ -1,18 +1,161 @@
 use anchor_lang::prelude::*;
+use ephemeral_rollups_sdk::anchor::MagicProgram;
+use ephemeral_rollups_sdk::consts::MAGIC_CONTEXT_ID;
+use ephemeral_rollups_sdk::ephem::{commit_accounts, commit_and_undelegate_accounts};
 
+use crate::constants::*;
 use crate::errors::*;
+use crate::events::*;
 use crate::state::*;
 
 #[derive(Accounts)]
 pub struct PlayTurn<'info> {
     #[account(mut)]
     pub game: Account<'info, GameState>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    /// The player's wallet, or their session key when `session` is passed
+    #[account(mut)]
     pub player: Signer<'info>,
+    #[account(
+        seeds = [SESSION_SEED, game.key().as_ref(), session.player.as_ref()],
+        bump = session.bump,
+    )]
+    pub session: Option<Account<'info, SessionKey>>,
+    /// Pass both magic accounts while the game is delegated so the winning
+    /// play commits and undelegates it automatically
+    pub magic_program: Option<Program<'info, MagicProgram>>,
+    /// CHECK: Magic context, checked by address
+    #[account(mut, address = MAGIC_CONTEXT_ID)]
+    pub magic_context: Option<AccountInfo<'info>>,
+}
+
+impl<'info> PlayTurn<'info> {
+    /// The wallet whose turn this is: the signer itself, or the player a
+    /// valid session key acts for.
+    fn acting_player(&self) -> Result<Pubkey> {
+        match &self.session {
+            Some(session) => {
+                session.is_valid(
+                    &self.game.key(),
+                    &self.player.key(),
+                    Clock::get()?.unix_timestamp,
+                )?;
+                Ok(session.player)
+            }
+            None => Ok(self.player.key()),
+        }
+    }
+
+    /// Pass the turn on and checkpoint to base layer if the game's commit
+    /// policy asks for it.
+    fn end_turn(&mut self, opened_meld: bool) -> Result<()> {
+        self.game.next_turn();
+        self.game.last_activity = Clock::get()?.unix_timestamp;
+
+        if !self.game.commit_due(opened_meld) {
+            return Ok(());
+        }
+        // A skipped checkpoint would leave the recovery snapshot further
+        // behind than the policy promises
+        let (Some(magic_context), Some(magic_program)) = (&self.magic_context, &self.magic_program)
+        else {
+            return err!(RummikubError::CommitAccountsRequired);
+        };
+        self.game.last_committed_turn = self.game.turn_number;
+        self.game.exit(&crate::ID)?;
+        commit_accounts(
+            &self.player,
+            vec![&self.game.to_account_info()],
+            magic_context,
+            magic_program,
+        )?;
+        msg!("Checkpoint committed at turn {}", self.game.turn_number);
+        Ok(())
+    }
+
+    /// A player went out. Matches with rounds to go clear the table for the
+    /// next one, to be dealt by `callback_shuffle`, and checkpoint; otherwise
+    /// the game finishes.
+    fn finish_round(&mut self, round_winner: usize) -> Result<()> {
+        let Some(winner_index) = self.game.end_round(round_winner)? else {
+            self.game.last_activity = Clock::get()?.unix_timestamp;
+            if let (Some(magic_context), Some(magic_program)) =
+                (&self.magic_context, &self.magic_program)
+            {
+                self.game.last_committed_turn = self.game.turn_number;
+                self.game.exit(&crate::ID)?;
+                commit_accounts(
+                    &self.player,
+                    vec![&self.game.to_account_info()],
+                    magic_context,
+                    magic_program,
+                )?;
+            }
+
+            emit!(RoundFinished {
+                game_id: self.game.game_id,
+                round: self.game.rounds_played,
+                round_winner: self.game.seat(round_winner).pubkey,
+                match_scores: self.game.seated().map(|p| p.match_score).collect(),
+            });
+            return Ok(());
+        };
+        self.finish_game(winner_index)
+    }
+
+    /// End the game for the winning seat and, if the game is delegated, hand it
+    /// back to base layer so `claim_prize` can run there.
+    fn finish_game(&mut self, winner_index: usize) -> Result<()> {
+        self.game.last_activity = Clock::get()?.unix_timestamp;
+        self.game.finished_at = self.game.last_activity;
+
+        let undelegating = match (&self.magic_context, &self.magic_program) {
+            (Some(magic_context), Some(magic_program)) => {
+                self.game.last_committed_turn = self.game.turn_number;
+                self.game.commit_policy = CommitPolicy::Manual;
+                self.game.exit(&crate::ID)?;
+                commit_and_undelegate_accounts(
+                    &self.player,
+                    vec![&self.game.to_account_info()],
+                    magic_context,
+                    magic_program,
+                )?;
+                true
+            }
+            _ => false,
+        };
+
+        emit!(GameFinished {
+            game_id: self.game.game_id,
+            winner: self.game.seat(winner_index).pubkey,
+            winner_score: self.game.seat(winner_index).score,
+            undelegating,
+        });
+
+        if self.game.rated {
+            let seated = || self.game.seated();
+            emit!(RatingsUpdated {
+                game_id: self.game.game_id,
+                players: seated().map(|p| p.pubkey).collect(),
+                ratings: seated().map(|p| p.rating).collect(),
+                deltas: seated().map(|p| p.rating_delta).collect(),
+            });
+        }
+
+        msg!(
+            "Player {} won the game!",
+            self.game.seat(winner_index).pubkey
+        );
+        Ok(())
+    }
 }
 
 pub fn draw_tile(ctx: Context<PlayTurn>) -> Result<()> {
+    require!(!ctx.accounts.config.play_paused, RummikubError::PlayPaused);
+
+    let player = &ctx.accounts.acting_player()?;
     let game = &mut ctx.accounts.game;
-    let player = &ctx.accounts.player.key();
 
     game.verify_turn(player)?;
     require!(
@@ -22,7 +165,7 @@ pub fn draw_tile(ctx: Context<PlayTurn>) -> Result<()> {
 
     let player_index = game.get_player_index(player)?;
     game.draw_tile_for_player(player_index)?;
-    game.next_turn();
+    ctx.accounts.end_turn(false)?;
 
     msg!("Player drew a tile");
     Ok(())
@@ -33,8 +176,10 @@ pub fn play_tiles(
     played_tiles: Vec<TilePlay>,
     new_table_melds: Vec<Meld>,
 ) -> Result<()> {
+    require!(!ctx.accounts.config.play_paused, RummikubError::PlayPaused);
+
+    let player = &ctx.accounts.acting_player()?;
     let game = &mut ctx.accounts.game;
-    let player = &ctx.accounts.player.key();
 
     game.verify_turn(player)?;
     require!(
@@ -43,16 +188,17 @@ pub fn play_tiles(
     );
 
     let player_index = game.get_player_index(player)?;
+    let had_opened = game.seat(player_index).has_opened;
 
     // Validate and execute the play
     game.execute_play(player_index, played_tiles, new_table_melds)?;
 
     // Check if player won
-    if game.players[player_index].tile_count == 0 {
-        game.end_game(player_index)?;
-        msg!("Player {} won the game!", player);
+    if game.seat(player_index).tile_count == 0 {
+        ctx.accounts.finish_round(player_index)?;
     } else {
-        game.next_turn();
+        let opened_meld = !had_opened && game.seat(player_index).has_opened;
+        ctx.accounts.end_turn(opened_meld)?;
     }
 
     Ok(())
@@ -64,8 +210,10 @@ pub fn play_with_joker_retrieval(
     played_tiles: Vec<TilePlay>,
     new_table_melds: Vec<Meld>,
 ) -> Result<()> {
+    require!(!ctx.accounts.config.play_paused, RummikubError::PlayPaused);
+
+    let player = &ctx.accounts.acting_player()?;
     let game = &mut ctx.accounts.game;
-    let player = &ctx.accounts.player.key();
 
     game.verify_turn(player)?;
     require!(
@@ -77,7 +225,7 @@ pub fn play_with_joker_retrieval(
 
     // Must have opened to retrieve jokers
     require!(
-        game.players[player_index].has_opened,
+        game.seat(player_index).has_opened,
         RummikubError::CannotRetrieveJokerBeforeOpening
     );
 
@@ -96,13 +244,7 @@ pub fn play_with_joker_retrieval(
 
     // Add retrieved jokers to player's hand
     for joker in retrieved_jokers {
-        require!(
-            game.players[player_index].tile_count < 21,
-            RummikubError::TooManyTiles
-        );
-        let count = game.players[player_index].tile_count as usize;
-        game.players[player_index].tiles[count] = joker;
-        game.players[player_index].tile_count += 1;
+        game.seat_mut(player_index).add_tile(joker);
     }
 
     // Now verify that the played tiles include all retrieved jokers
@@ -111,7 +253,7 @@ pub fn play_with_joker_retrieval(
     let mut jokers_in_new_melds = 0;
     for meld in &new_table_melds {
         for tile in &meld.tiles {
-            if tile.tile_type == TileType::Joker {
+            if tile.tile_type() == TileType::Joker {
                 jokers_in_new_melds += 1;
             }
         }
@@ -128,11 +270,10 @@ pub fn play_with_joker_retrieval(
     game.execute_play(player_index, played_tiles, new_table_melds)?;
 
     // Check if player won
-    if game.players[player_index].tile_count == 0 {
-        game.end_game(player_index)?;
-        msg!("Player {} won the game!", player);
+    if game.seat(player_index).tile_count == 0 {
+        ctx.accounts.finish_round(player_index)?;
     } else {
-        game.next_turn();
+        ctx.accounts.end_turn(false)?;
     }
 
     msg!(
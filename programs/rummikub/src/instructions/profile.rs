@@ -0,0 +1,157 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct CreateProfile<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + PlayerProfile::SPACE,
+        seeds = [PROFILE_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, PlayerProfile>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateProfile<'info> {
+    #[account(
+        mut,
+        seeds = [PROFILE_SEED, owner.key().as_ref()],
+        bump = profile.bump,
+        has_one = owner,
+    )]
+    pub profile: Account<'info, PlayerProfile>,
+    pub owner: Signer<'info>,
+}
+
+/// Permissionless: anyone may write a finished game's result into the
+/// profile of a player who joined with one attached.
+#[derive(Accounts)]
+pub struct RecordResult<'info> {
+    #[account(
+        mut,
+        seeds = [GAME_SEED, &game.game_id.to_le_bytes()],
+        bump = game.bump,
+    )]
+    pub game: Account<'info, GameState>,
+    #[account(
+        mut,
+        seeds = [PROFILE_SEED, profile.owner.as_ref()],
+        bump = profile.bump,
+    )]
+    pub profile: Account<'info, PlayerProfile>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    /// Attach the season the game finished in to earn season points.
+    /// Required while a season is active, so a result can't be recorded
+    /// without the points it earns there
+    #[account(
+        mut,
+        seeds = [SEASON_SEED, &season.season_id.to_le_bytes()],
+        bump = season.bump,
+    )]
+    pub season: Option<Account<'info, Season>>,
+}
+
+pub fn create_profile(ctx: Context<CreateProfile>, display_name: String) -> Result<()> {
+    require!(
+        display_name.len() <= MAX_DISPLAY_NAME_LEN,
+        RummikubError::DisplayNameTooLong
+    );
+
+    let profile = &mut ctx.accounts.profile;
+    profile.owner = ctx.accounts.owner.key();
+    profile.display_name = display_name;
+    profile.games_played = 0;
+    profile.games_won = 0;
+    profile.tiles_played = 0;
+    profile.best_winning_score = 0;
+    profile.lifetime_winnings = 0;
+    profile.rating = INITIAL_RATING;
+    profile.rated_games = 0;
+    profile.season_id = 0;
+    profile.season_points = 0;
+    profile.bump = ctx.bumps.profile;
+
+    msg!("Profile created for {}", profile.owner);
+    Ok(())
+}
+
+pub fn set_display_name(ctx: Context<UpdateProfile>, display_name: String) -> Result<()> {
+    require!(
+        display_name.len() <= MAX_DISPLAY_NAME_LEN,
+        RummikubError::DisplayNameTooLong
+    );
+
+    ctx.accounts.profile.display_name = display_name;
+
+    msg!("Display name updated");
+    Ok(())
+}
+
+pub fn record_result(ctx: Context<RecordResult>) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+    let profile = &mut ctx.accounts.profile;
+
+    require!(
+        game.game_status == GameStatus::Finished,
+        RummikubError::GameNotFinished
+    );
+
+    let player_index = game.get_player_index(&profile.owner)?;
+    let player = game.seat_mut(player_index);
+    require!(player.has_profile, RummikubError::InvalidProfile);
+    require!(
+        !player.result_recorded,
+        RummikubError::ResultAlreadyRecorded
+    );
+
+    // Mark recorded BEFORE updating the profile so it is only counted once
+    player.result_recorded = true;
+    let won = game.is_winner(player_index);
+    // Both claim paths pay the same shares, and a bankroll claim inside the
+    // ER cannot write profiles, so winnings are credited here
+    let winnings = game.winnings(player_index);
+    profile.record(game.seat(player_index), won, winnings, game.rated);
+
+    match &mut ctx.accounts.season {
+        Some(season) if season.contains(game.finished_at) => {
+            require!(!season.finalized, RummikubError::SeasonFinalized);
+
+            if profile.season_id != season.season_id {
+                profile.season_id = season.season_id;
+                profile.season_points = 0;
+            }
+            profile.season_points += SEASON_POINTS_PLAYED;
+            if won {
+                profile.season_points += SEASON_POINTS_WON;
+            }
+            season.submit(profile.owner, profile.season_points);
+        }
+        // The active season not covering the game shows no points are lost
+        Some(season) => require!(
+            season.season_id == ctx.accounts.config.active_season,
+            RummikubError::SeasonNotActive
+        ),
+        None => require!(
+            ctx.accounts.config.active_season == 0,
+            RummikubError::SeasonRequired
+        ),
+    }
+
+    msg!(
+        "Recorded game {} for {} (won: {}, rating: {})",
+        game.game_id,
+        profile.owner,
+        won,
+        profile.rating
+    );
+    Ok(())
+}
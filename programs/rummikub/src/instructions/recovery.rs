@@ -0,0 +1,454 @@
+use anchor_lang::prelude::*;
+use ephemeral_rollups_sdk::anchor::DelegationProgram;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::instructions::lobby::{list_game, unlist_game};
+use crate::legacy::load_game;
+use crate::state::*;
+
+/// Restart a game whose rollup stalled from its last committed snapshot,
+/// under a new game id on base layer. Needs a majority of the seated players:
+/// the caller plus the other players' wallets as signing remaining accounts.
+#[derive(Accounts)]
+#[instruction(new_game_id: u64)]
+pub struct ResumeGame<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    /// CHECK: Stalled game, still owned by the delegation program. Verified
+    /// in `load_stalled_snapshot`
+    pub snapshot: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, &escrow.game_id.to_le_bytes()],
+        bump = escrow.bump,
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        init,
+        payer = caller,
+        space = snapshot.data_len(),
+        seeds = [GAME_SEED, &new_game_id.to_le_bytes()],
+        bump
+    )]
+    pub game: Account<'info, GameState>,
+    #[account(
+        init,
+        payer = caller,
+        space = 8 + Escrow::SPACE,
+        seeds = [ESCROW_SEED, &new_game_id.to_le_bytes()],
+        bump
+    )]
+    pub new_escrow: Account<'info, Escrow>,
+    /// Required when the stalled game is listed in the open-game registry;
+    /// a game still filling is listed again there under its new id
+    #[account(
+        mut,
+        seeds = [LOBBY_SEED, &lobby.page.to_le_bytes()],
+        bump = lobby.bump,
+    )]
+    pub lobby: Option<Account<'info, LobbyPage>>,
+    #[account(mut)]
+    pub caller: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Pay out a game whose rollup stalled from its last committed snapshot.
+/// Remaining accounts: the seated players' wallets, in seat order. Either mode
+/// needs a majority of them to sign, counting the caller.
+#[derive(Accounts)]
+pub struct SettleStalledGame<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    /// CHECK: Stalled game, still owned by the delegation program. Verified
+    /// in `load_stalled_snapshot`
+    pub snapshot: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, &escrow.game_id.to_le_bytes()],
+        bump = escrow.bump,
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump,
+    )]
+    /// CHECK: Treasury PDA to collect house fees
+    pub treasury: AccountInfo<'info>,
+    /// Required when the game is listed in the open-game registry
+    #[account(
+        mut,
+        seeds = [LOBBY_SEED, &lobby.page.to_le_bytes()],
+        bump = lobby.bump,
+    )]
+    pub lobby: Option<Account<'info, LobbyPage>>,
+    pub caller: Signer<'info>,
+}
+
+/// Return the stakes of a stalled bankroll-funded game from its last committed
+/// snapshot. Remaining accounts: the seated players' bankrolls, in seat order,
+/// then the other players' wallets as signers; needs a majority of the table.
+#[derive(Accounts)]
+pub struct SettleStalledBankrollGame<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    /// CHECK: Stalled game, still owned by the delegation program. Verified
+    /// in `load_stalled_snapshot`
+    pub snapshot: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, &escrow.game_id.to_le_bytes()],
+        bump = escrow.bump,
+    )]
+    pub escrow: Account<'info, Escrow>,
+    /// Required when the game is listed in the open-game registry
+    #[account(
+        mut,
+        seeds = [LOBBY_SEED, &lobby.page.to_le_bytes()],
+        bump = lobby.bump,
+    )]
+    pub lobby: Option<Account<'info, LobbyPage>>,
+    pub caller: Signer<'info>,
+}
+
+pub fn resume_game(ctx: Context<ResumeGame>, new_game_id: u64) -> Result<()> {
+    require!(
+        new_game_id < FIRST_COUNTER_GAME_ID,
+        RummikubError::ReservedGameId
+    );
+    let snapshot = load_stalled_snapshot(
+        &ctx.accounts.snapshot,
+        &ctx.accounts.escrow,
+        &ctx.accounts.config,
+    )?;
+    // The base-layer copy can look stalled while the game is still live in
+    // the ER, so one player alone may not restart it
+    let signers = signing_players(ctx.accounts.caller.key(), ctx.remaining_accounts);
+    require!(
+        snapshot.has_player_majority(&signers),
+        RummikubError::Unauthorized
+    );
+    require!(
+        !snapshot.bankroll_funded,
+        RummikubError::StakeSourceMismatch
+    );
+    require!(
+        snapshot.game_status != GameStatus::Cancelled,
+        RummikubError::InvalidGameState
+    );
+
+    // Mark the old escrow settled BEFORE moving its funds
+    let amount = snapshot.prize_pool;
+    ctx.accounts.escrow.settled = true;
+    **ctx
+        .accounts
+        .escrow
+        .to_account_info()
+        .try_borrow_mut_lamports()? -= amount;
+    **ctx
+        .accounts
+        .new_escrow
+        .to_account_info()
+        .try_borrow_mut_lamports()? += amount;
+
+    let new_escrow = &mut ctx.accounts.new_escrow;
+    new_escrow.game_id = new_game_id;
+    new_escrow.bump = ctx.bumps.new_escrow;
+    new_escrow.settled = false;
+
+    // The old id's entry goes; a game still filling is listed again under
+    // the new one
+    unlist_game(&snapshot, ctx.accounts.lobby.as_deref_mut())?;
+
+    let game_id = snapshot.game_id;
+    let turn_number = snapshot.turn_number;
+    ctx.accounts.game.set_inner(GameState {
+        game_id: new_game_id,
+        bump: ctx.bumps.game,
+        commit_policy: CommitPolicy::Manual,
+        commit_frequency_ms: 0,
+        last_committed_turn: turn_number,
+        last_activity: Clock::get()?.unix_timestamp,
+        lobby_page: None,
+        ..snapshot
+    });
+
+    if let Some(lobby) = ctx.accounts.lobby.as_mut() {
+        if ctx.accounts.game.game_status == GameStatus::WaitingForPlayers {
+            list_game(&mut ctx.accounts.game, lobby)?;
+        }
+    }
+
+    emit!(GameResumed {
+        game_id,
+        new_game_id,
+        turn_number,
+    });
+
+    msg!(
+        "Game {} resumed as game {} from turn {}",
+        game_id,
+        new_game_id,
+        turn_number
+    );
+    Ok(())
+}
+
+pub fn settle_stalled_game<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SettleStalledGame<'info>>,
+    mode: RecoveryMode,
+) -> Result<()> {
+    let mut snapshot = load_stalled_snapshot(
+        &ctx.accounts.snapshot,
+        &ctx.accounts.escrow,
+        &ctx.accounts.config,
+    )?;
+    let caller = ctx.accounts.caller.key();
+
+    require!(
+        snapshot.get_player_index(&caller).is_ok(),
+        RummikubError::Unauthorized
+    );
+    require!(
+        !snapshot.bankroll_funded,
+        RummikubError::StakeSourceMismatch
+    );
+    require!(
+        snapshot.game_status == GameStatus::WaitingForPlayers
+            || snapshot.game_status == GameStatus::InProgress,
+        RummikubError::InvalidGameState
+    );
+
+    let seated = snapshot.current_players as usize;
+    require!(
+        ctx.remaining_accounts.len() == seated,
+        RummikubError::InvalidRefundAccount
+    );
+    for (i, account) in ctx.remaining_accounts.iter().enumerate() {
+        require!(
+            account.key() == snapshot.seat(i).pubkey && account.is_writable,
+            RummikubError::InvalidRefundAccount
+        );
+    }
+
+    // The base-layer copy can look stalled while the game is still live in
+    // the ER, so voiding or scoring it needs a majority of the table
+    let signers = signing_players(caller, ctx.remaining_accounts);
+    require!(
+        snapshot.has_player_majority(&signers),
+        RummikubError::Unauthorized
+    );
+
+    // Mark the escrow settled BEFORE moving lamports
+    let prize_pool = snapshot.prize_pool;
+    ctx.accounts.escrow.settled = true;
+    unlist_game(&snapshot, ctx.accounts.lobby.as_deref_mut())?;
+    let escrow_info = ctx.accounts.escrow.to_account_info();
+
+    let winner = match mode {
+        RecoveryMode::Refund => {
+            let refund = prize_pool / seated as u64;
+            for account in ctx.remaining_accounts.iter() {
+                **escrow_info.try_borrow_mut_lamports()? -= refund;
+                **account.try_borrow_mut_lamports()? += refund;
+            }
+
+            msg!(
+                "Stalled game refunded {} SOL to each of {} players",
+                refund as f64 / 1_000_000_000.0,
+                seated
+            );
+            None
+        }
+        RecoveryMode::ScoreHands => {
+            require!(
+                snapshot.game_status == GameStatus::InProgress,
+                RummikubError::GameNotInProgress
+            );
+
+            // A stalled match ends with this round
+            let winner_index = snapshot.end_game(snapshot.lowest_hand())?;
+
+            // Calculate house fee (5%)
+            let house_fee = (prize_pool as u128)
+                .checked_mul(HOUSE_FEE_BPS as u128)
+                .unwrap()
+                .checked_div(10000)
+                .unwrap() as u64;
+            let winner_prize = prize_pool.checked_sub(house_fee).unwrap();
+
+            **escrow_info.try_borrow_mut_lamports()? -= house_fee;
+            **ctx.accounts.treasury.try_borrow_mut_lamports()? += house_fee;
+            **escrow_info.try_borrow_mut_lamports()? -= winner_prize;
+
+            // Team games split the prize between both partners
+            match snapshot.partner(winner_index) {
+                Some(partner) => {
+                    let share = winner_prize / 2;
+                    **ctx.remaining_accounts[partner].try_borrow_mut_lamports()? += share;
+                    **ctx.remaining_accounts[winner_index].try_borrow_mut_lamports()? +=
+                        winner_prize - share;
+                }
+                None => {
+                    **ctx.remaining_accounts[winner_index].try_borrow_mut_lamports()? +=
+                        winner_prize;
+                }
+            }
+
+            msg!(
+                "Stalled game scored. Winner: {} SOL, House fee: {} SOL",
+                winner_prize as f64 / 1_000_000_000.0,
+                house_fee as f64 / 1_000_000_000.0
+            );
+            Some(snapshot.seat(winner_index).pubkey)
+        }
+    };
+
+    emit!(StalledGameSettled {
+        game_id: snapshot.game_id,
+        mode,
+        winner,
+        scores: snapshot.seated().map(|p| p.score).collect(),
+    });
+    Ok(())
+}
+
+pub fn settle_stalled_bankroll_game<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SettleStalledBankrollGame<'info>>,
+) -> Result<()> {
+    let snapshot = load_stalled_snapshot(
+        &ctx.accounts.snapshot,
+        &ctx.accounts.escrow,
+        &ctx.accounts.config,
+    )?;
+    let caller = ctx.accounts.caller.key();
+
+    require!(
+        snapshot.get_player_index(&caller).is_ok(),
+        RummikubError::Unauthorized
+    );
+    require!(snapshot.bankroll_funded, RummikubError::StakeSourceMismatch);
+    require!(
+        snapshot.game_status == GameStatus::WaitingForPlayers
+            || snapshot.game_status == GameStatus::InProgress,
+        RummikubError::InvalidGameState
+    );
+
+    let seated = snapshot.current_players as usize;
+    require!(
+        ctx.remaining_accounts.len() >= seated,
+        RummikubError::InvalidBankrollAccount
+    );
+    let (bankrolls, wallets) = ctx.remaining_accounts.split_at(seated);
+
+    // Same rule as `settle_stalled_game`: the snapshot can look stalled while
+    // the game is still being played in the ER
+    let signers = signing_players(caller, wallets);
+    require!(
+        snapshot.has_player_majority(&signers),
+        RummikubError::Unauthorized
+    );
+
+    // Mark the escrow settled BEFORE unlocking, so the snapshot pays once
+    ctx.accounts.escrow.settled = true;
+    unlist_game(&snapshot, ctx.accounts.lobby.as_deref_mut())?;
+
+    let stake = snapshot.prize_pool / seated as u64;
+    let mut unlocked = 0;
+    for (i, account) in bankrolls.iter().enumerate() {
+        let (expected, _) = Pubkey::find_program_address(
+            &[BANKROLL_SEED, snapshot.seat(i).pubkey.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(
+            account.key(),
+            expected,
+            RummikubError::InvalidBankrollAccount
+        );
+
+        // A bankroll delegated to the stalled ER is frozen at its last commit,
+        // and bankrolls are never committed with a stake locked, so that copy
+        // still holds the stake in `balance`. Only bankrolls that stayed on
+        // base layer while the game was delegated have one to unlock
+        if *account.owner == DelegationProgram::id() {
+            continue;
+        }
+        let mut bankroll = Account::<Bankroll>::try_from(account)?;
+        require!(account.is_writable, RummikubError::InvalidBankrollAccount);
+        bankroll.locked = bankroll
+            .locked
+            .checked_sub(stake)
+            .ok_or(RummikubError::InvalidBankrollAccount)?;
+        bankroll.balance += stake;
+        bankroll.exit(&crate::ID)?;
+        unlocked += 1;
+    }
+
+    msg!(
+        "Stalled bankroll game: unlocked {} SOL in {} of {} bankrolls",
+        stake as f64 / 1_000_000_000.0,
+        unlocked,
+        seated
+    );
+
+    emit!(StalledGameSettled {
+        game_id: snapshot.game_id,
+        mode: RecoveryMode::Refund,
+        winner: None,
+        scores: snapshot.seated().map(|p| p.score).collect(),
+    });
+    Ok(())
+}
+
+/// The caller and every remaining account that signed
+pub fn signing_players(caller: Pubkey, accounts: &[AccountInfo]) -> Vec<Pubkey> {
+    std::iter::once(caller)
+        .chain(
+            accounts
+                .iter()
+                .filter(|account| account.is_signer)
+                .map(|account| account.key()),
+        )
+        .collect()
+}
+
+/// Read the last committed state of a delegated game from base layer and
+/// check that its escrow can still be recovered.
+fn load_stalled_snapshot(
+    info: &AccountInfo,
+    escrow: &Escrow,
+    config: &Config,
+) -> Result<GameState> {
+    require_keys_eq!(
+        *info.owner,
+        DelegationProgram::id(),
+        RummikubError::GameNotDelegated
+    );
+
+    let game = {
+        let game_data = info.try_borrow_data()?;
+        // Games delegated before an upgrade still hold an earlier layout. v0
+        // games have no escrow, so the escrow account check refuses them
+        load_game(&game_data)?
+    };
+    let expected = Pubkey::create_program_address(
+        &[GAME_SEED, &game.game_id.to_le_bytes(), &[game.bump]],
+        &crate::ID,
+    )
+    .map_err(|_| RummikubError::InvalidGameState)?;
+    require_keys_eq!(info.key(), expected, RummikubError::InvalidGameState);
+    require!(
+        escrow.game_id == game.game_id,
+        RummikubError::InvalidGameState
+    );
+    require!(!escrow.settled, RummikubError::EscrowSettled);
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        now >= game.last_activity + config.recovery_timeout,
+        RummikubError::RecoveryTimeoutNotReached
+    );
+    Ok(game)
+}
@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::instructions::lobby::list_game;
+use crate::state::*;
+
+/// Restart a finished, claimed game on the same account.
+/// Remaining accounts: the wallets of the players who want another round,
+/// each signing and paying a new entry fee. A player who played the last game
+/// with a profile passes it right after their wallet.
+#[derive(Accounts)]
+pub struct Rematch<'info> {
+    #[account(
+        mut,
+        seeds = [GAME_SEED, &game.game_id.to_le_bytes()],
+        bump = game.bump,
+    )]
+    pub game: Account<'info, GameState>,
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, &game.game_id.to_le_bytes()],
+        bump = escrow.bump,
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    /// Attach to list the rematch's open seats in the open-game registry
+    #[account(
+        mut,
+        seeds = [LOBBY_SEED, &lobby.page.to_le_bytes()],
+        bump = lobby.bump,
+    )]
+    pub lobby: Option<Account<'info, LobbyPage>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn rematch<'info>(ctx: Context<'_, '_, 'info, 'info, Rematch<'info>>) -> Result<()> {
+    require!(!ctx.accounts.config.paused, RummikubError::GamePaused);
+
+    let game = &ctx.accounts.game;
+    // Bankroll stakes live in the ER and tournament tables are seated by the
+    // tournament, so only plain escrow games can be replayed
+    require!(
+        !game.bankroll_funded && game.tournament.is_none(),
+        RummikubError::InvalidGameState
+    );
+    require!(!ctx.accounts.escrow.settled, RummikubError::EscrowSettled);
+    require!(
+        game.seated().all(|p| !p.has_profile || p.result_recorded),
+        RummikubError::ResultNotRecorded
+    );
+
+    // Each opted-in wallet, with the rating from its profile if it has one
+    let mut opted_in: Vec<(&AccountInfo<'info>, Option<u16>)> = Vec::new();
+    let mut accounts = ctx.remaining_accounts.iter();
+    while let Some(account) = accounts.next() {
+        require!(account.is_signer, RummikubError::Unauthorized);
+        require!(
+            !opted_in.iter().any(|(a, _)| a.key() == account.key()),
+            RummikubError::InvalidGameState
+        );
+        let index = game.get_player_index(&account.key())?;
+
+        // Ratings come from the live profile, as in `join_game`
+        let rating = if game.seat(index).has_profile {
+            let info = accounts.next().ok_or(RummikubError::ProfileRequired)?;
+            let profile = Account::<PlayerProfile>::try_from(info)?;
+            require_keys_eq!(profile.owner, account.key(), RummikubError::InvalidProfile);
+            Some(profile.rating)
+        } else {
+            None
+        };
+        opted_in.push((account, rating));
+    }
+    require!(!opted_in.is_empty(), RummikubError::InvalidGameState);
+
+    let entry_fee = game.entry_fee;
+    for (account, _) in opted_in.iter() {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: (*account).clone(),
+                    to: ctx.accounts.escrow.to_account_info(),
+                },
+            ),
+            entry_fee,
+        )?;
+    }
+
+    let game = &mut ctx.accounts.game;
+    let previous = game.reset_for_rematch(Clock::get()?.unix_timestamp)?;
+
+    // Re-seat starting from the old second seat, so a different player opens
+    for offset in 1..=previous.len() {
+        let old = &previous[offset % previous.len()];
+        let Some((_, rating)) = opted_in.iter().find(|(a, _)| a.key() == old.pubkey) else {
+            continue;
+        };
+
+        let seat = game.add_player(old.pubkey, entry_fee)?;
+        game.seat_mut(seat).has_profile = rating.is_some();
+        if game.rated {
+            game.seat_mut(seat).rating = rating.ok_or(RummikubError::ProfileRequired)?;
+        }
+    }
+
+    if game.game_status == GameStatus::WaitingForPlayers {
+        if let Some(lobby) = ctx.accounts.lobby.as_mut() {
+            list_game(game, lobby)?;
+        }
+    }
+
+    msg!(
+        "Rematch of game {}: {} of {} players back, {} seats open",
+        game.game_id,
+        game.current_players,
+        previous.len(),
+        game.max_players - game.current_players
+    );
+    Ok(())
+}
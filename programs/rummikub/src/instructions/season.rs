@@ -0,0 +1,192 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::state::*;
+
+/// Create a season and make it the one house fees are routed to.
+#[derive(Accounts)]
+#[instruction(season_id: u64)]
+pub struct CreateSeason<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ RummikubError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Season::SPACE,
+        seeds = [SEASON_SEED, &season_id.to_le_bytes()],
+        bump
+    )]
+    pub season: Account<'info, Season>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FundSeason<'info> {
+    #[account(
+        mut,
+        seeds = [SEASON_SEED, &season.season_id.to_le_bytes()],
+        bump = season.bump,
+    )]
+    pub season: Account<'info, Season>,
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless once the season has ended.
+/// Remaining accounts: the wallets on the leaderboard, in rank order.
+#[derive(Accounts)]
+pub struct FinalizeSeason<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [SEASON_SEED, &season.season_id.to_le_bytes()],
+        bump = season.bump,
+    )]
+    pub season: Account<'info, Season>,
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump,
+    )]
+    /// CHECK: Treasury PDA, receives shares of ranks nobody reached
+    pub treasury: AccountInfo<'info>,
+}
+
+pub fn create_season(
+    ctx: Context<CreateSeason>,
+    season_id: u64,
+    start_ts: i64,
+    end_ts: i64,
+    payout_bps: Vec<u16>,
+) -> Result<()> {
+    require!(
+        season_id != 0 && start_ts < end_ts,
+        RummikubError::InvalidSeason
+    );
+    require!(
+        payout_bps.len() <= SEASON_LEADERBOARD_SIZE
+            && payout_bps.iter().map(|&bps| bps as u64).sum::<u64>() <= 10000,
+        RummikubError::InvalidPayoutShares
+    );
+
+    let season = &mut ctx.accounts.season;
+    season.season_id = season_id;
+    season.start_ts = start_ts;
+    season.end_ts = end_ts;
+    season.reward_pool = 0;
+    season.finalized = false;
+    season.bump = ctx.bumps.season;
+    season.payout_bps = payout_bps;
+    season.leaderboard = Vec::new();
+
+    ctx.accounts.config.active_season = season_id;
+
+    msg!(
+        "Season {} created, running {} to {}",
+        season_id,
+        start_ts,
+        end_ts
+    );
+    Ok(())
+}
+
+pub fn fund_season(ctx: Context<FundSeason>, amount: u64) -> Result<()> {
+    require!(amount > 0, RummikubError::InvalidAmount);
+    require!(
+        !ctx.accounts.season.finalized,
+        RummikubError::SeasonFinalized
+    );
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.funder.to_account_info(),
+                to: ctx.accounts.season.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    let season = &mut ctx.accounts.season;
+    season.reward_pool += amount;
+
+    msg!(
+        "Season {} reward pool: {} SOL",
+        season.season_id,
+        season.reward_pool as f64 / 1_000_000_000.0
+    );
+    Ok(())
+}
+
+pub fn finalize_season(ctx: Context<FinalizeSeason>) -> Result<()> {
+    let season = &mut ctx.accounts.season;
+
+    require!(!season.finalized, RummikubError::SeasonFinalized);
+    require!(
+        Clock::get()?.unix_timestamp >= season.end_ts,
+        RummikubError::SeasonNotEnded
+    );
+
+    let paid_ranks = season.payout_bps.len().min(season.leaderboard.len());
+    require!(
+        ctx.remaining_accounts.len() == paid_ranks,
+        RummikubError::InvalidRefundAccount
+    );
+
+    // Mark finalized BEFORE moving lamports
+    let reward_pool = season.reward_pool;
+    season.reward_pool = 0;
+    season.finalized = true;
+
+    let season_info = season.to_account_info();
+    let mut paid = 0u64;
+    for (rank, account) in ctx.remaining_accounts.iter().enumerate() {
+        require!(
+            account.key() == season.leaderboard[rank].player && account.is_writable,
+            RummikubError::InvalidRefundAccount
+        );
+
+        let share = (reward_pool as u128)
+            .checked_mul(season.payout_bps[rank] as u128)
+            .unwrap()
+            .checked_div(10000)
+            .unwrap() as u64;
+        **season_info.try_borrow_mut_lamports()? -= share;
+        **account.try_borrow_mut_lamports()? += share;
+        paid += share;
+    }
+
+    // Unclaimed ranks and rounding go to the house
+    let leftover = reward_pool - paid;
+    **season_info.try_borrow_mut_lamports()? -= leftover;
+    **ctx.accounts.treasury.try_borrow_mut_lamports()? += leftover;
+
+    let config = &mut ctx.accounts.config;
+    if config.active_season == season.season_id {
+        config.active_season = 0;
+    }
+
+    msg!(
+        "Season {} finalized. Paid {} SOL to {} players",
+        season.season_id,
+        paid as f64 / 1_000_000_000.0,
+        paid_ranks
+    );
+    Ok(())
+}
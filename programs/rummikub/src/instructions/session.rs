@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::state::*;
+
+/// Sessions can be registered while the game is delegated, so the game is
+/// only checked by address.
+#[derive(Accounts)]
+pub struct RegisterSession<'info> {
+    #[account(
+        init,
+        payer = player,
+        space = 8 + SessionKey::SPACE,
+        seeds = [SESSION_SEED, game.key().as_ref(), player.key().as_ref()],
+        bump
+    )]
+    pub session: Account<'info, SessionKey>,
+    /// CHECK: Game PDA, verified against its own game_id and bump
+    pub game: AccountInfo<'info>,
+    #[account(mut)]
+    pub player: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeSession<'info> {
+    #[account(
+        mut,
+        seeds = [SESSION_SEED, session.game.as_ref(), player.key().as_ref()],
+        bump = session.bump,
+        has_one = player,
+        close = player,
+    )]
+    pub session: Account<'info, SessionKey>,
+    #[account(mut)]
+    pub player: Signer<'info>,
+}
+
+pub fn register_session(
+    ctx: Context<RegisterSession>,
+    session_key: Pubkey,
+    duration_secs: i64,
+) -> Result<()> {
+    require!(
+        duration_secs > 0 && duration_secs <= MAX_SESSION_DURATION,
+        RummikubError::InvalidSessionDuration
+    );
+
+    // The game may be owned by the delegation program, so read it raw
+    let game = {
+        let game_data = ctx.accounts.game.try_borrow_data()?;
+        GameState::try_deserialize_unchecked(&mut &game_data[..])?
+    };
+    let expected = Pubkey::create_program_address(
+        &[GAME_SEED, &game.game_id.to_le_bytes(), &[game.bump]],
+        &crate::ID,
+    )
+    .map_err(|_| RummikubError::InvalidSessionKey)?;
+    require_keys_eq!(
+        ctx.accounts.game.key(),
+        expected,
+        RummikubError::InvalidSessionKey
+    );
+
+    let session = &mut ctx.accounts.session;
+    session.game = ctx.accounts.game.key();
+    session.player = ctx.accounts.player.key();
+    session.session_key = session_key;
+    session.expires_at = Clock::get()?.unix_timestamp + duration_secs;
+    session.bump = ctx.bumps.session;
+
+    msg!(
+        "Session key {} registered for {} until {}",
+        session_key,
+        session.player,
+        session.expires_at
+    );
+    Ok(())
+}
+
+pub fn revoke_session(ctx: Context<RevokeSession>) -> Result<()> {
+    msg!("Session key {} revoked", ctx.accounts.session.session_key);
+    Ok(())
+}
@@ -0,0 +1,538 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use ephemeral_rollups_sdk::anchor::DelegationProgram;
+
+use crate::constants::*;
+use crate::errors::*;
+use crate::instructions::recovery::signing_players;
+use crate::legacy::load_game;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(tournament_id: u64)]
+pub struct CreateTournament<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Tournament::SPACE,
+        seeds = [TOURNAMENT_SEED, &tournament_id.to_le_bytes()],
+        bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterTournament<'info> {
+    #[account(
+        mut,
+        seeds = [TOURNAMENT_SEED, &tournament.tournament_id.to_le_bytes()],
+        bump = tournament.bump,
+    )]
+    pub tournament: Account<'info, Tournament>,
+    #[account(mut)]
+    pub player: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StartRound<'info> {
+    #[account(
+        mut,
+        seeds = [TOURNAMENT_SEED, &tournament.tournament_id.to_le_bytes()],
+        bump = tournament.bump,
+        has_one = authority @ RummikubError::Unauthorized,
+    )]
+    pub tournament: Account<'info, Tournament>,
+    pub authority: Signer<'info>,
+}
+
+/// Organizer, or anyone once registration has been open for
+/// `TOURNAMENT_REGISTRATION_WINDOW` without reaching two entrants.
+/// Remaining accounts: the entrants' wallets, in registration order.
+#[derive(Accounts)]
+pub struct CancelTournament<'info> {
+    #[account(
+        mut,
+        seeds = [TOURNAMENT_SEED, &tournament.tournament_id.to_le_bytes()],
+        bump = tournament.bump,
+    )]
+    pub tournament: Account<'info, Tournament>,
+    pub caller: Signer<'info>,
+}
+
+/// Permissionless crank: creates one table of the current round as a regular
+/// game, seated and started, through the same setup path as
+/// `initialize_game`. The id comes from the game counter, whose range
+/// `initialize_game` cannot take. The payer (a wallet, or another program's
+/// PDA over CPI) pays the rent and becomes the game's authority, so it can
+/// reclaim it with `close_game` once the table is reported.
+#[derive(Accounts)]
+pub struct CreateTableGame<'info> {
+    #[account(
+        mut,
+        seeds = [TOURNAMENT_SEED, &tournament.tournament_id.to_le_bytes()],
+        bump = tournament.bump,
+    )]
+    pub tournament: Account<'info, Tournament>,
+    #[account(
+        mut,
+        seeds = [COUNTER_SEED],
+        bump = counter.bump,
+    )]
+    pub counter: Account<'info, GameCounter>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + GameState::space(tournament.table_size),
+        seeds = [GAME_SEED, &counter.next_game_id.to_le_bytes()],
+        bump
+    )]
+    pub game: Account<'info, GameState>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Escrow::SPACE,
+        seeds = [ESCROW_SEED, &counter.next_game_id.to_le_bytes()],
+        bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless: feed a finished table game back into the tournament.
+#[derive(Accounts)]
+pub struct ReportTable<'info> {
+    #[account(
+        mut,
+        seeds = [TOURNAMENT_SEED, &tournament.tournament_id.to_le_bytes()],
+        bump = tournament.bump,
+    )]
+    pub tournament: Account<'info, Tournament>,
+    #[account(
+        seeds = [GAME_SEED, &game.game_id.to_le_bytes()],
+        bump = game.bump,
+    )]
+    pub game: Account<'info, GameState>,
+}
+
+/// Once a table's game has had no turn for the config's `recovery_timeout`,
+/// the player on turn forfeits and the lowest rack among the others wins the
+/// table. Permissionless for a game on base layer; a delegated game needs a
+/// majority of the seated players, the caller plus the others' wallets as
+/// signing remaining accounts.
+#[derive(Accounts)]
+pub struct ForfeitTable<'info> {
+    #[account(
+        mut,
+        seeds = [TOURNAMENT_SEED, &tournament.tournament_id.to_le_bytes()],
+        bump = tournament.bump,
+    )]
+    pub tournament: Account<'info, Tournament>,
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    /// CHECK: Table game, on base layer or delegated (its last committed
+    /// state is read then). Verified in `forfeit_table`
+    pub game: AccountInfo<'info>,
+    pub caller: Signer<'info>,
+}
+
+/// Permissionless once the tournament has finished.
+/// Remaining accounts: the wallets of the paid places, in standings order.
+#[derive(Accounts)]
+pub struct DistributeTournamentPrizes<'info> {
+    #[account(
+        mut,
+        seeds = [TOURNAMENT_SEED, &tournament.tournament_id.to_le_bytes()],
+        bump = tournament.bump,
+    )]
+    pub tournament: Account<'info, Tournament>,
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump,
+    )]
+    /// CHECK: Treasury PDA, receives whatever the payout shares leave over
+    pub treasury: AccountInfo<'info>,
+}
+
+pub fn create_tournament(
+    ctx: Context<CreateTournament>,
+    tournament_id: u64,
+    format: TournamentFormat,
+    table_size: u8,
+    buy_in: u64,
+    max_entrants: u8,
+    payout_bps: Vec<u16>,
+) -> Result<()> {
+    require!(!ctx.accounts.config.paused, RummikubError::GamePaused);
+    require!(
+        table_size >= MIN_PLAYERS as u8 && table_size <= SIX_PLAYER_SEATS as u8,
+        RummikubError::InvalidPlayerCount
+    );
+    require!(
+        max_entrants >= MIN_PLAYERS as u8 && max_entrants as usize <= MAX_TOURNAMENT_ENTRANTS,
+        RummikubError::InvalidTournament
+    );
+    if let TournamentFormat::Swiss { rounds } = format {
+        require!(rounds > 0, RummikubError::InvalidTournament);
+    }
+    require!(
+        payout_bps.len() <= MAX_TOURNAMENT_PAYOUTS
+            && payout_bps.iter().map(|&bps| bps as u64).sum::<u64>() <= 10000,
+        RummikubError::InvalidPayoutShares
+    );
+
+    let tournament = &mut ctx.accounts.tournament;
+    tournament.tournament_id = tournament_id;
+    tournament.authority = ctx.accounts.authority.key();
+    tournament.format = format;
+    tournament.table_size = table_size;
+    tournament.buy_in = buy_in;
+    tournament.max_entrants = max_entrants;
+    tournament.status = TournamentStatus::Registration;
+    tournament.current_round = 0;
+    tournament.prize_pool = 0;
+    tournament.bump = ctx.bumps.tournament;
+    tournament.created_at = Clock::get()?.unix_timestamp;
+    tournament.payout_bps = payout_bps;
+    tournament.entrants = Vec::new();
+    tournament.seating = Vec::new();
+    tournament.tables = Vec::new();
+
+    msg!(
+        "Tournament {} created. Buy-in: {} SOL, tables of {}",
+        tournament_id,
+        buy_in as f64 / 1_000_000_000.0,
+        table_size
+    );
+    Ok(())
+}
+
+pub fn register_tournament(ctx: Context<RegisterTournament>) -> Result<()> {
+    let player = ctx.accounts.player.key();
+    let tournament = &ctx.accounts.tournament;
+
+    require!(
+        tournament.status == TournamentStatus::Registration,
+        RummikubError::RegistrationClosed
+    );
+    require!(
+        tournament.entrants.len() < tournament.max_entrants as usize,
+        RummikubError::TournamentFull
+    );
+    require!(
+        !tournament.entrants.iter().any(|e| e.player == player),
+        RummikubError::AlreadyRegistered
+    );
+
+    let buy_in = tournament.buy_in;
+    if buy_in > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.player.to_account_info(),
+                    to: ctx.accounts.tournament.to_account_info(),
+                },
+            ),
+            buy_in,
+        )?;
+    }
+
+    let tournament = &mut ctx.accounts.tournament;
+    tournament.prize_pool += buy_in;
+    tournament.entrants.push(Entrant {
+        player,
+        points: 0,
+        eliminated_round: 0,
+    });
+
+    msg!(
+        "Player {} registered ({} of {})",
+        player,
+        tournament.entrants.len(),
+        tournament.max_entrants
+    );
+    Ok(())
+}
+
+pub fn start_round(ctx: Context<StartRound>) -> Result<()> {
+    let tournament = &mut ctx.accounts.tournament;
+
+    match tournament.status {
+        TournamentStatus::Registration => {}
+        TournamentStatus::Running => {
+            require!(tournament.round_complete(), RummikubError::RoundInProgress)
+        }
+        TournamentStatus::Finished | TournamentStatus::Cancelled => {
+            return err!(RummikubError::InvalidTournament)
+        }
+    }
+
+    tournament.status = TournamentStatus::Running;
+    tournament.seat_round()?;
+
+    msg!(
+        "Round {} seated at {} tables",
+        tournament.current_round,
+        tournament.tables.len()
+    );
+    Ok(())
+}
+
+pub fn cancel_tournament(ctx: Context<CancelTournament>) -> Result<()> {
+    let tournament = &mut ctx.accounts.tournament;
+
+    require!(
+        tournament.status == TournamentStatus::Registration,
+        RummikubError::RegistrationClosed
+    );
+    if ctx.accounts.caller.key() != tournament.authority {
+        require!(
+            tournament.entrants.len() < MIN_PLAYERS,
+            RummikubError::Unauthorized
+        );
+        require!(
+            Clock::get()?.unix_timestamp >= tournament.created_at + TOURNAMENT_REGISTRATION_WINDOW,
+            RummikubError::RegistrationStillOpen
+        );
+    }
+    require!(
+        ctx.remaining_accounts.len() == tournament.entrants.len(),
+        RummikubError::InvalidRefundAccount
+    );
+
+    // Mark cancelled and empty the pool BEFORE moving lamports
+    tournament.status = TournamentStatus::Cancelled;
+    tournament.prize_pool = 0;
+
+    let buy_in = tournament.buy_in;
+    let tournament_info = tournament.to_account_info();
+    for (entrant, account) in tournament.entrants.iter().zip(ctx.remaining_accounts) {
+        require!(
+            account.key() == entrant.player && account.is_writable,
+            RummikubError::InvalidRefundAccount
+        );
+        **tournament_info.try_borrow_mut_lamports()? -= buy_in;
+        **account.try_borrow_mut_lamports()? += buy_in;
+    }
+
+    msg!(
+        "Tournament {} cancelled, refunded {} SOL to each of {} entrants",
+        tournament.tournament_id,
+        buy_in as f64 / 1_000_000_000.0,
+        tournament.entrants.len()
+    );
+    Ok(())
+}
+
+pub fn create_table_game(ctx: Context<CreateTableGame>, table_index: u8) -> Result<()> {
+    let tournament = &mut ctx.accounts.tournament;
+    let table = *tournament
+        .tables
+        .get(table_index as usize)
+        .ok_or(RummikubError::InvalidTable)?;
+    require!(
+        tournament.status == TournamentStatus::Running && table.size > 1 && table.game_id == 0,
+        RummikubError::InvalidTable
+    );
+
+    let counter = &mut ctx.accounts.counter;
+    let game_id = counter.next_game_id;
+    counter.next_game_id += 1;
+    tournament.tables[table_index as usize].game_id = game_id;
+
+    let game = &mut ctx.accounts.game;
+    game.setup(
+        game_id,
+        ctx.accounts.payer.key(),
+        table.size,
+        GameOptions::for_players(table.size),
+        ctx.bumps.game,
+        Clock::get()?.unix_timestamp,
+    )?;
+    game.tournament = Some(tournament.key());
+
+    // Buy-ins already paid: seat everyone without a stake, which starts the game
+    for position in table.first..table.first + table.size {
+        let entrant = tournament.seating[position as usize] as usize;
+        game.add_player(tournament.entrants[entrant].player, 0)?;
+    }
+
+    let escrow = &mut ctx.accounts.escrow;
+    escrow.game_id = game_id;
+    escrow.bump = ctx.bumps.escrow;
+    escrow.settled = false;
+
+    msg!(
+        "Round {} table {} started as game {}",
+        tournament.current_round,
+        table_index,
+        game_id
+    );
+    Ok(())
+}
+
+pub fn report_table(ctx: Context<ReportTable>) -> Result<()> {
+    let tournament = &mut ctx.accounts.tournament;
+    let game = &ctx.accounts.game;
+
+    require!(
+        game.tournament == Some(tournament.key()),
+        RummikubError::InvalidTable
+    );
+    require!(
+        game.game_status == GameStatus::Finished,
+        RummikubError::GameNotFinished
+    );
+
+    let table_index = tournament
+        .tables
+        .iter()
+        .position(|t| t.size > 1 && t.game_id == game.game_id)
+        .ok_or(RummikubError::InvalidTable)?;
+    let table = tournament.tables[table_index];
+    require!(
+        table.winner == NO_WINNER,
+        RummikubError::TableAlreadyReported
+    );
+
+    // Seats were filled in seating order
+    let winner = game.winner.ok_or(RummikubError::GameNotFinished)?;
+    let seat = game.get_player_index(&winner)?;
+    tournament.apply_result(table_index, table.first + seat as u8);
+
+    msg!(
+        "Game {} won by {}{}",
+        game.game_id,
+        winner,
+        if tournament.status == TournamentStatus::Finished {
+            ". Tournament finished"
+        } else {
+            ""
+        }
+    );
+    Ok(())
+}
+
+pub fn forfeit_table(ctx: Context<ForfeitTable>) -> Result<()> {
+    let info = &ctx.accounts.game;
+    require!(
+        *info.owner == crate::ID || *info.owner == DelegationProgram::id(),
+        RummikubError::InvalidTable
+    );
+    let game = load_game(&info.try_borrow_data()?)?;
+    let expected = Pubkey::create_program_address(
+        &[GAME_SEED, &game.game_id.to_le_bytes(), &[game.bump]],
+        &crate::ID,
+    )
+    .map_err(|_| RummikubError::InvalidGameState)?;
+    require_keys_eq!(info.key(), expected, RummikubError::InvalidGameState);
+
+    let tournament = &mut ctx.accounts.tournament;
+    require!(
+        game.tournament == Some(tournament.key()),
+        RummikubError::InvalidTable
+    );
+    require!(
+        game.game_status == GameStatus::InProgress,
+        RummikubError::GameNotInProgress
+    );
+    require!(
+        Clock::get()?.unix_timestamp >= game.last_activity + ctx.accounts.config.recovery_timeout,
+        RummikubError::RecoveryTimeoutNotReached
+    );
+    // The base-layer copy of a delegated game can look stalled while it is
+    // still live in the ER, so one player alone may not award it
+    if *info.owner == DelegationProgram::id() {
+        let signers = signing_players(ctx.accounts.caller.key(), ctx.remaining_accounts);
+        require!(
+            game.has_player_majority(&signers),
+            RummikubError::Unauthorized
+        );
+    }
+
+    let table_index = tournament
+        .tables
+        .iter()
+        .position(|t| t.size > 1 && t.game_id == game.game_id)
+        .ok_or(RummikubError::InvalidTable)?;
+    let table = tournament.tables[table_index];
+    require!(
+        table.winner == NO_WINNER,
+        RummikubError::TableAlreadyReported
+    );
+
+    // Seats were filled in seating order
+    let seat = game.lowest_hand_after_forfeit();
+    tournament.apply_result(table_index, table.first + seat as u8);
+
+    msg!(
+        "Game {} stalled on {}; table awarded to {}",
+        game.game_id,
+        game.seat(game.current_turn as usize).pubkey,
+        game.seat(seat).pubkey
+    );
+    Ok(())
+}
+
+pub fn distribute_tournament_prizes(ctx: Context<DistributeTournamentPrizes>) -> Result<()> {
+    let tournament = &mut ctx.accounts.tournament;
+
+    require!(
+        tournament.status == TournamentStatus::Finished,
+        RummikubError::TournamentNotFinished
+    );
+    require!(tournament.prize_pool > 0, RummikubError::PrizesAlreadyPaid);
+
+    let standings = tournament.standings();
+    let paid_places = tournament.payout_bps.len().min(standings.len());
+    require!(
+        ctx.remaining_accounts.len() == paid_places,
+        RummikubError::InvalidRefundAccount
+    );
+
+    // Set prize pool to 0 BEFORE moving lamports
+    let prize_pool = tournament.prize_pool;
+    tournament.prize_pool = 0;
+
+    let tournament_info = tournament.to_account_info();
+    let mut paid = 0u64;
+    for (place, account) in ctx.remaining_accounts.iter().enumerate() {
+        let entrant = &tournament.entrants[standings[place] as usize];
+        require!(
+            account.key() == entrant.player && account.is_writable,
+            RummikubError::InvalidRefundAccount
+        );
+
+        let share = (prize_pool as u128)
+            .checked_mul(tournament.payout_bps[place] as u128)
+            .unwrap()
+            .checked_div(10000)
+            .unwrap() as u64;
+        **tournament_info.try_borrow_mut_lamports()? -= share;
+        **account.try_borrow_mut_lamports()? += share;
+        paid += share;
+    }
+
+    // Whatever the shares leave over is the house cut
+    let leftover = prize_pool - paid;
+    **tournament_info.try_borrow_mut_lamports()? -= leftover;
+    **ctx.accounts.treasury.try_borrow_mut_lamports()? += leftover;
+
+    msg!(
+        "Tournament {} paid {} SOL to {} places, {} SOL to the house",
+        tournament.tournament_id,
+        paid as f64 / 1_000_000_000.0,
+        paid_places,
+        leftover as f64 / 1_000_000_000.0
+    );
+    Ok(())
+}
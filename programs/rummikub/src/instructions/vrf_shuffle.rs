@@ -69,6 +69,17 @@ pub fn callback_shuffle(ctx: Context<CallbackShuffle>, randomness: [u8; 32]) ->
         RummikubError::InvalidGameState
     );
 
+    // A match between rounds is dealt from this randomness
+    if game.awaiting_deal() {
+        game.deal_round_with_randomness(randomness)?;
+        msg!(
+            "Round {} dealt with VRF randomness for game_id: {}",
+            game.rounds_played + 1,
+            game.game_id
+        );
+        return Ok(());
+    }
+
     // Use the VRF randomness to shuffle the tile pool
     game.shuffle_tiles_with_randomness(randomness)?;
 
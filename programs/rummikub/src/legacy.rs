@@ -0,0 +1,142 @@
+//! Earlier `GameState` layouts, frozen as they were stored on chain. They are
+//! read by `migrate_game`, which upgrades them one version at a time to the
+//! current layout.
+//!
+//! - v0: the original layout: 3-byte tiles, fixed 21-tile racks, Anchor's
+//!   default `GameState` discriminator
+//! - v1 on: `VERSIONED_GAME_PREFIX` followed by the version byte
+//!
+//! To change the layout, copy the current `GameState` here as the next
+//! `GameStateVn`, bump `GAME_VERSION` and add its arm to `load_game`.
+
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::RummikubError;
+use crate::state::*;
+
+/// Decode a game account in any layout, upgraded to the current one
+pub fn load_game(data: &[u8]) -> Result<GameState> {
+    match game_version(data) {
+        Some(0) => Ok(GameStateV0::deserialize(&mut &data[8..])?.into()),
+        Some(GAME_VERSION) => GameState::try_deserialize(&mut &data[..]),
+        Some(_) => err!(RummikubError::UnsupportedGameVersion),
+        None => err!(ErrorCode::AccountDiscriminatorMismatch),
+    }
+}
+
+/// Layout version of a stored game, if it is a game account at all
+pub fn game_version(data: &[u8]) -> Option<u8> {
+    if data.starts_with(LEGACY_GAME_DISCRIMINATOR) {
+        Some(0)
+    } else if data.starts_with(VERSIONED_GAME_PREFIX) {
+        data.get(8).copied()
+    } else {
+        None
+    }
+}
+
+// v0
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct TileV0 {
+    pub tile_type: TileType,
+}
+
+impl From<TileV0> for Tile {
+    fn from(tile: TileV0) -> Tile {
+        match tile.tile_type {
+            TileType::Number { color, number } => Tile::number(color, number),
+            TileType::Joker => Tile::JOKER,
+            TileType::Empty => Tile::default(),
+        }
+    }
+}
+
+/// Fixed rack of the original layout; only the first `tile_count` slots hold tiles
+pub const RACK_SLOTS_V0: usize = 21;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct PlayerV0 {
+    pub pubkey: Pubkey,
+    pub tiles: [TileV0; RACK_SLOTS_V0],
+    pub tile_count: u8,
+    pub has_opened: bool,
+    pub score: i16,
+}
+
+impl From<PlayerV0> for Player {
+    fn from(player: PlayerV0) -> Player {
+        let count = (player.tile_count as usize).min(RACK_SLOTS_V0);
+        Player {
+            pubkey: player.pubkey,
+            tiles: convert_tiles(player.tiles[..count].to_vec()),
+            tile_count: count as u8,
+            has_opened: player.has_opened,
+            score: player.score,
+            ..Player::default()
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MeldV0 {
+    pub meld_type: MeldType,
+    pub tiles: Vec<TileV0>,
+}
+
+impl From<MeldV0> for Meld {
+    fn from(meld: MeldV0) -> Meld {
+        Meld {
+            meld_type: meld.meld_type,
+            tiles: convert_tiles(meld.tiles),
+        }
+    }
+}
+
+/// The layout the program was first deployed with. Entry fees were held in
+/// the game account itself
+#[derive(AnchorSerialize, AnchorDeserialize, Default)]
+pub struct GameStateV0 {
+    pub game_id: u64,
+    pub authority: Pubkey,
+    pub max_players: u8,
+    pub current_players: u8,
+    pub current_turn: u8,
+    pub game_status: GameStatus,
+    pub winner: Option<Pubkey>,
+    pub prize_pool: u64,
+    pub bump: u8,
+    pub players: [PlayerV0; MAX_PLAYERS],
+    pub table_melds: Vec<MeldV0>,
+    pub tile_pool: Vec<TileV0>,
+    pub tiles_remaining: u16,
+}
+
+// Fields added after v0 take the values a game created with default options
+// would have
+impl From<GameStateV0> for GameState {
+    fn from(game: GameStateV0) -> GameState {
+        GameState {
+            game_id: game.game_id,
+            authority: game.authority,
+            max_players: game.max_players,
+            current_players: game.current_players,
+            current_turn: game.current_turn,
+            game_status: game.game_status,
+            winner: game.winner,
+            prize_pool: game.prize_pool,
+            bump: game.bump,
+            players: game.players.map(Player::from),
+            table_melds: game.table_melds.into_iter().map(Meld::from).collect(),
+            tile_pool: convert_tiles(game.tile_pool),
+            tiles_remaining: game.tiles_remaining,
+            entry_fee: ENTRY_FEE_LAMPORTS,
+            ..GameState::default()
+        }
+    }
+}
+
+fn convert_tiles(tiles: Vec<TileV0>) -> Vec<Tile> {
+    tiles.into_iter().map(Tile::from).collect()
+}
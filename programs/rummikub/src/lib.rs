@@ -5,7 +5,9 @@ declare_id!("8ygsQKbh1oBBhmm3Fva7oC8B4xxjw22R8UVUcoUoM6hi");
 
 pub mod constants;
 pub mod errors;
+pub mod events;
 pub mod instructions;
+pub mod legacy;
 pub mod state;
 
 #[cfg(test)]
@@ -23,8 +25,25 @@ pub mod rummikub {
         ctx: Context<InitializeGame>,
         game_id: u64,
         max_players: u8,
+        options: state::GameOptions,
+        access: Option<state::GameAccess>,
     ) -> Result<()> {
-        instructions::initialize::initialize_game(ctx, game_id, max_players)
+        instructions::initialize::initialize_game(ctx, game_id, max_players, options, access)
+    }
+
+    /// Create the global counter that assigns game ids
+    pub fn initialize_game_counter(ctx: Context<InitializeGameCounter>) -> Result<()> {
+        instructions::initialize::initialize_game_counter(ctx)
+    }
+
+    /// Initialize a game under the next id from the game counter, returning the id
+    pub fn initialize_next_game(
+        ctx: Context<InitializeNextGame>,
+        max_players: u8,
+        options: state::GameOptions,
+        access: Option<state::GameAccess>,
+    ) -> Result<u64> {
+        instructions::initialize::initialize_next_game(ctx, max_players, options, access)
     }
 
     /// Join an existing game with 0.1 SOL entry fee
@@ -32,6 +51,21 @@ pub mod rummikub {
         instructions::join::join_game(ctx)
     }
 
+    /// Join a password-protected game
+    pub fn join_game_with_password(ctx: Context<JoinGame>, password: Vec<u8>) -> Result<()> {
+        instructions::join::join_game_with_password(ctx, password)
+    }
+
+    /// Remove a player from a game that has not started and refund their stake (authority)
+    pub fn kick_player(ctx: Context<KickPlayer>) -> Result<()> {
+        instructions::join::kick_player(ctx)
+    }
+
+    /// Restart a finished, claimed game with the players who pay in again
+    pub fn rematch<'info>(ctx: Context<'_, '_, 'info, 'info, Rematch<'info>>) -> Result<()> {
+        instructions::rematch::rematch(ctx)
+    }
+
     /// Draw a tile from the pool
     pub fn draw_tile(ctx: Context<PlayTurn>) -> Result<()> {
         instructions::play::draw_tile(ctx)
@@ -61,14 +95,249 @@ pub mod rummikub {
         )
     }
 
+    /// Create the global config; callable once by the program upgrade authority
+    pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()> {
+        instructions::admin::initialize_config(ctx)
+    }
+
+    /// Pause new games and joins, and optionally turns in running games
+    pub fn set_pause(ctx: Context<UpdateConfig>, paused: bool, play_paused: bool) -> Result<()> {
+        instructions::admin::set_pause(ctx, paused, play_paused)
+    }
+
+    /// Replace the allowlist of ER validators games and bankrolls may be delegated to
+    pub fn set_validators(ctx: Context<UpdateConfig>, validators: Vec<Pubkey>) -> Result<()> {
+        instructions::admin::set_validators(ctx, validators)
+    }
+
+    /// Cancel a game while paused and refund every player's entry fee
+    pub fn emergency_settle<'info>(
+        ctx: Context<'_, '_, 'info, 'info, EmergencySettle<'info>>,
+    ) -> Result<()> {
+        instructions::emergency::emergency_settle(ctx)
+    }
+
+    /// Set how long a delegated game must be idle before it can be recovered
+    pub fn set_recovery_timeout(ctx: Context<UpdateConfig>, recovery_timeout: i64) -> Result<()> {
+        instructions::admin::set_recovery_timeout(ctx, recovery_timeout)
+    }
+
+    /// Restart a stalled delegated game from its last committed snapshot under a new id
+    pub fn resume_game(ctx: Context<ResumeGame>, new_game_id: u64) -> Result<()> {
+        instructions::recovery::resume_game(ctx, new_game_id)
+    }
+
+    /// Refund or score a stalled delegated game from its last committed snapshot
+    pub fn settle_stalled_game<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleStalledGame<'info>>,
+        mode: state::RecoveryMode,
+    ) -> Result<()> {
+        instructions::recovery::settle_stalled_game(ctx, mode)
+    }
+
+    /// Unlock the stakes of a stalled bankroll-funded game from its last
+    /// committed snapshot
+    pub fn settle_stalled_bankroll_game<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleStalledBankrollGame<'info>>,
+    ) -> Result<()> {
+        instructions::recovery::settle_stalled_bankroll_game(ctx)
+    }
+
+    /// Route a share of each house fee to the active season's reward pool
+    pub fn set_season_fee(ctx: Context<UpdateConfig>, season_fee_bps: u16) -> Result<()> {
+        instructions::admin::set_season_fee(ctx, season_fee_bps)
+    }
+
+    /// Create a season with its leaderboard payout shares and make it active
+    pub fn create_season(
+        ctx: Context<CreateSeason>,
+        season_id: u64,
+        start_ts: i64,
+        end_ts: i64,
+        payout_bps: Vec<u16>,
+    ) -> Result<()> {
+        instructions::season::create_season(ctx, season_id, start_ts, end_ts, payout_bps)
+    }
+
+    /// Add SOL to a season's reward pool
+    pub fn fund_season(ctx: Context<FundSeason>, amount: u64) -> Result<()> {
+        instructions::season::fund_season(ctx, amount)
+    }
+
+    /// Pay out a season's reward pool to its leaderboard once it has ended
+    pub fn finalize_season(ctx: Context<FinalizeSeason>) -> Result<()> {
+        instructions::season::finalize_season(ctx)
+    }
+
+    /// Open a page of the open-game registry
+    pub fn open_lobby_page(ctx: Context<OpenLobbyPage>, page: u32) -> Result<()> {
+        instructions::lobby::open_lobby_page(ctx, page)
+    }
+
+    /// Refresh or drop registry entries for games that filled up or went away
+    pub fn prune_lobby(ctx: Context<PruneLobby>, game_ids: Vec<u64>) -> Result<()> {
+        instructions::lobby::prune_lobby(ctx, game_ids)
+    }
+
+    /// Open a matchmaking queue for one stake and table size (admin)
+    pub fn open_queue(
+        ctx: Context<OpenQueue>,
+        stake: u64,
+        player_count: u8,
+        rating_band: u16,
+    ) -> Result<()> {
+        instructions::matchmaking::open_queue(ctx, stake, player_count, rating_band)
+    }
+
+    /// Pay the queue's stake and wait for a table
+    pub fn enqueue(ctx: Context<Enqueue>) -> Result<()> {
+        instructions::matchmaking::enqueue(ctx)
+    }
+
+    /// Leave a matchmaking queue and get the stake back
+    pub fn leave_queue(ctx: Context<LeaveQueue>) -> Result<()> {
+        instructions::matchmaking::leave_queue(ctx)
+    }
+
+    /// Create, seat and start a game from queued players (permissionless crank)
+    pub fn match_players(ctx: Context<MatchPlayers>, game_id: u64) -> Result<()> {
+        instructions::matchmaking::match_players(ctx, game_id)
+    }
+
+    /// Create a buy-in tournament (single elimination or Swiss)
+    pub fn create_tournament(
+        ctx: Context<CreateTournament>,
+        tournament_id: u64,
+        format: state::TournamentFormat,
+        table_size: u8,
+        buy_in: u64,
+        max_entrants: u8,
+        payout_bps: Vec<u16>,
+    ) -> Result<()> {
+        instructions::tournament::create_tournament(
+            ctx,
+            tournament_id,
+            format,
+            table_size,
+            buy_in,
+            max_entrants,
+            payout_bps,
+        )
+    }
+
+    /// Pay the buy-in and enter a tournament
+    pub fn register_tournament(ctx: Context<RegisterTournament>) -> Result<()> {
+        instructions::tournament::register_tournament(ctx)
+    }
+
+    /// Seat the next tournament round
+    pub fn start_round(ctx: Context<StartRound>) -> Result<()> {
+        instructions::tournament::start_round(ctx)
+    }
+
+    /// Cancel a tournament still in registration and refund every buy-in
+    pub fn cancel_tournament(ctx: Context<CancelTournament>) -> Result<()> {
+        instructions::tournament::cancel_tournament(ctx)
+    }
+
+    /// Create and start the game for one table of the current round
+    pub fn create_table_game(ctx: Context<CreateTableGame>, table_index: u8) -> Result<()> {
+        instructions::tournament::create_table_game(ctx, table_index)
+    }
+
+    /// Advance a tournament with the result of a finished table game
+    pub fn report_table(ctx: Context<ReportTable>) -> Result<()> {
+        instructions::tournament::report_table(ctx)
+    }
+
+    /// Decide a stalled table against the player whose turn it is
+    pub fn forfeit_table(ctx: Context<ForfeitTable>) -> Result<()> {
+        instructions::tournament::forfeit_table(ctx)
+    }
+
+    /// Pay a finished tournament's prize pool by final standings
+    pub fn distribute_tournament_prizes(ctx: Context<DistributeTournamentPrizes>) -> Result<()> {
+        instructions::tournament::distribute_tournament_prizes(ctx)
+    }
+
     /// Claim prize after winning (95% to winner, 5% house fee)
     pub fn claim_prize(ctx: Context<ClaimPrize>) -> Result<()> {
         instructions::claim::claim_prize(ctx)
     }
 
+    /// Create the caller's prepaid bankroll
+    pub fn open_bankroll(ctx: Context<OpenBankroll>) -> Result<()> {
+        instructions::bankroll::open_bankroll(ctx)
+    }
+
+    /// Deposit SOL into a bankroll (base layer)
+    pub fn deposit(ctx: Context<FundBankroll>, amount: u64) -> Result<()> {
+        instructions::bankroll::deposit(ctx, amount)
+    }
+
+    /// Withdraw available SOL from a bankroll (base layer)
+    pub fn withdraw(ctx: Context<FundBankroll>, amount: u64) -> Result<()> {
+        instructions::bankroll::withdraw(ctx, amount)
+    }
+
+    /// Join a game paying the entry fee from the player's bankroll (works inside the ER)
+    pub fn join_game_with_bankroll(ctx: Context<JoinGameWithBankroll>) -> Result<()> {
+        instructions::bankroll::join_game_with_bankroll(ctx)
+    }
+
+    /// Credit the prize of a bankroll-funded game to the winner's bankroll (works inside the ER)
+    pub fn claim_prize_to_bankroll<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimPrizeToBankroll<'info>>,
+    ) -> Result<()> {
+        instructions::bankroll::claim_prize_to_bankroll(ctx)
+    }
+
+    /// Move house fees held in a bankroll to the treasury (base layer)
+    pub fn sweep_bankroll_fees(ctx: Context<SweepBankrollFees>) -> Result<()> {
+        instructions::bankroll::sweep_bankroll_fees(ctx)
+    }
+
+    /// Close a claimed or cancelled game and return its rent to the authority
+    pub fn close_game(ctx: Context<CloseGame>) -> Result<()> {
+        instructions::close::close_game(ctx)
+    }
+
+    /// Register an expiring session key that may take turns in one game
+    pub fn register_session(
+        ctx: Context<RegisterSession>,
+        session_key: Pubkey,
+        duration_secs: i64,
+    ) -> Result<()> {
+        instructions::session::register_session(ctx, session_key, duration_secs)
+    }
+
+    /// Revoke a session key, returning its rent to the player
+    pub fn revoke_session(ctx: Context<RevokeSession>) -> Result<()> {
+        instructions::session::revoke_session(ctx)
+    }
+
+    /// Create the caller's player profile
+    pub fn create_profile(ctx: Context<CreateProfile>, display_name: String) -> Result<()> {
+        instructions::profile::create_profile(ctx, display_name)
+    }
+
+    /// Change the display name on the caller's profile
+    pub fn set_display_name(ctx: Context<UpdateProfile>, display_name: String) -> Result<()> {
+        instructions::profile::set_display_name(ctx, display_name)
+    }
+
+    /// Write a finished game's result into a player's profile (permissionless)
+    pub fn record_result(ctx: Context<RecordResult>) -> Result<()> {
+        instructions::profile::record_result(ctx)
+    }
+
     /// Delegate game state to Ephemeral Rollup for fast, free gameplay
-    pub fn delegate(ctx: Context<DelegateGame>) -> Result<()> {
-        instructions::delegation::delegate(ctx)
+    pub fn delegate(
+        ctx: Context<DelegateGame>,
+        commit_policy: state::CommitPolicy,
+        commit_frequency_ms: u32,
+    ) -> Result<()> {
+        instructions::delegation::delegate(ctx, commit_policy, commit_frequency_ms)
     }
 
     /// Commit game state back to base layer
@@ -81,6 +350,21 @@ pub mod rummikub {
         instructions::delegation::undelegate(ctx)
     }
 
+    /// Delegate a bankroll to the Ephemeral Rollup alongside games
+    pub fn delegate_bankroll(ctx: Context<DelegateBankroll>) -> Result<()> {
+        instructions::delegation::delegate_bankroll(ctx)
+    }
+
+    /// Commit bankroll balances back to base layer
+    pub fn commit_bankroll(ctx: Context<CommitBankroll>) -> Result<()> {
+        instructions::delegation::commit_bankroll(ctx)
+    }
+
+    /// Commit and undelegate a bankroll
+    pub fn undelegate_bankroll(ctx: Context<CommitBankroll>) -> Result<()> {
+        instructions::delegation::undelegate_bankroll(ctx)
+    }
+
     /// Request VRF randomness to shuffle tiles
     pub fn request_shuffle(ctx: Context<RequestShuffle>, client_seed: u8) -> Result<()> {
         instructions::vrf_shuffle::request_shuffle(ctx, client_seed)
@@ -90,4 +374,9 @@ pub mod rummikub {
     pub fn callback_shuffle(ctx: Context<CallbackShuffle>, randomness: [u8; 32]) -> Result<()> {
         instructions::vrf_shuffle::callback_shuffle(ctx, randomness)
     }
+
+    /// Upgrade a game stored in an earlier layout to the current one
+    pub fn migrate_game(ctx: Context<MigrateGame>) -> Result<()> {
+        instructions::migrate::migrate_game(ctx)
+    }
 }
@@ -2,7 +2,8 @@ use anchor_lang::prelude::*;
 
 use crate::constants::*;
 
-#[account]
+#[account(discriminator = GAME_DISCRIMINATOR)]
+#[derive(Default)]
 pub struct GameState {
     pub game_id: u64,
     pub authority: Pubkey,
@@ -17,10 +18,30 @@ pub struct GameState {
     pub table_melds: Vec<Meld>,
     pub tile_pool: Vec<Tile>,
     pub tiles_remaining: u16,
+    pub bankroll_funded: bool, // Stakes are held in player bankrolls, not the escrow
+    pub commit_policy: CommitPolicy,
+    pub commit_frequency_ms: u32, // Validator checkpoint interval, 0 = default
+    pub turn_number: u32,         // Turns completed so far
+    pub last_committed_turn: u32, // Turn reflected by the base-layer copy
+    pub last_activity: i64,       // Last join or turn, used to detect stalled games
+    pub rated: bool,              // Finishing updates the players' profile ratings
+    pub finished_at: i64,         // When the winning play landed, 0 until then
+    pub tournament: Option<Pubkey>, // Tournament that seated this table
+    pub entry_fee: u64,           // Stake each player pays to join
+    pub lobby_page: Option<u32>,  // Lobby page listing this game while it fills
+    pub access: GameAccess,       // Who may join
+    pub match_target: Option<MatchTarget>, // Set for multi-round matches
+    pub rounds_played: u8,        // Completed rounds of a match
+    pub teams: bool,              // Seats 0 & 2 play 1 & 3
+    pub partner_melds: bool,      // Teams only: play onto the table once your partner has opened
+    pub rules: GameRules,         // Rule variant the game is played under
+    pub extra_players: Vec<Player>, // Seats 5 and 6 of a six-player game
+    pub payout: u64,              // Prize after the house fee, set when the game finishes
 }
 
 impl GameState {
     pub const SPACE: usize = 8 +      // discriminator
+        1 +      // version (last discriminator byte)
         8 +      // game_id
         32 +     // authority
         1 +      // max_players
@@ -31,15 +52,128 @@ impl GameState {
         8 +      // prize_pool
         1 +      // bump
         (Player::SPACE * MAX_PLAYERS) + // players array
-        4 + (Meld::SPACE * 30) +  // table_melds vec (max 30 melds on table)
-        4 + (Tile::SPACE * TOTAL_TILES) + // tile_pool vec
-        2; // tiles_remaining
+        4 + (Meld::SPACE * MAX_TABLE_MELDS) + // table_melds vec
+        4 + // tile_pool vec
+        (Tile::SPACE * TOTAL_TILES) + // every tile, in the pool, a rack or a meld
+        2 + // tiles_remaining
+        1 + // bankroll_funded
+        CommitPolicy::SPACE + // commit_policy
+        4 + // commit_frequency_ms
+        4 + // turn_number
+        4 + // last_committed_turn
+        8 + // last_activity
+        1 + // rated
+        8 + // finished_at
+        33 + // tournament
+        8 + // entry_fee
+        5 + // lobby_page
+        GameAccess::SPACE + // access
+        1 + MatchTarget::SPACE + // match_target
+        1 + // rounds_played
+        1 + // teams
+        1 + // partner_melds
+        GameRules::SPACE + // rules
+        4 + // extra_players vec (empty up to 4 players)
+        8; // payout
+
+    /// Account size for a game of `max_players`. Up to 4 players this is
+    /// `SPACE`; the six-player edition adds the extra seats and a 3-copy set.
+    pub fn space(max_players: u8) -> usize {
+        let extra_seats = (max_players as usize).saturating_sub(MAX_PLAYERS);
+        if extra_seats == 0 {
+            return Self::SPACE;
+        }
+        Self::SPACE
+            + extra_seats * Player::SPACE // extra_players
+            + (SIX_PLAYER_TILES - TOTAL_TILES) * Tile::SPACE // larger tile set
+            + extra_seats * 32 // access.reserved
+    }
+
+    /// Fresh game waiting for players. Shared by `initialize_game` and games
+    /// seated by a tournament.
+    pub fn setup(
+        &mut self,
+        game_id: u64,
+        authority: Pubkey,
+        max_players: u8,
+        options: GameOptions,
+        bump: u8,
+        now: i64,
+    ) -> Result<()> {
+        require!(
+            max_players >= MIN_PLAYERS as u8 && max_players <= SIX_PLAYER_SEATS as u8,
+            crate::errors::RummikubError::InvalidPlayerCount
+        );
+        if let Some(target) = options.match_target {
+            target.validate()?;
+        }
+        require!(
+            (!options.teams || max_players == 4) && (options.teams || !options.partner_melds),
+            crate::errors::RummikubError::InvalidTeamGame
+        );
+        options.rules.validate()?;
+        require!(
+            max_players as usize <= MAX_PLAYERS || options.rules.jokers == SIX_PLAYER_JOKERS,
+            crate::errors::RummikubError::InvalidRules
+        );
+
+        self.game_id = game_id;
+        self.authority = authority;
+        self.max_players = max_players;
+        self.current_players = 0;
+        self.current_turn = 0;
+        self.game_status = GameStatus::WaitingForPlayers;
+        self.winner = None;
+        self.prize_pool = 0;
+        self.bankroll_funded = false;
+        self.commit_policy = CommitPolicy::Manual;
+        self.commit_frequency_ms = 0;
+        self.turn_number = 0;
+        self.last_committed_turn = 0;
+        self.last_activity = now;
+        self.rated = options.rated;
+        self.match_target = options.match_target;
+        self.rounds_played = 0;
+        self.teams = options.teams;
+        self.partner_melds = options.partner_melds;
+        self.rules = options.rules;
+        self.finished_at = 0;
+        self.payout = 0;
+        self.tournament = None;
+        self.entry_fee = ENTRY_FEE_LAMPORTS;
+        self.lobby_page = None;
+        self.access = GameAccess::default();
+        self.players = Default::default();
+        self.extra_players =
+            vec![Player::default(); (max_players as usize).saturating_sub(MAX_PLAYERS)];
+        self.bump = bump;
+
+        // Initialize tile pool with all tiles
+        self.initialize_tile_pool()
+    }
+
+    /// Whether this game is played with the six-player edition's tile set
+    pub fn is_six_player(&self) -> bool {
+        self.max_players as usize > MAX_PLAYERS
+    }
 
     pub fn initialize_tile_pool(&mut self) -> Result<()> {
-        self.tile_pool = Vec::with_capacity(TOTAL_TILES);
+        self.build_tile_pool();
+
+        // Shuffle tiles (simple pseudo-random based on clock)
+        let clock = Clock::get()?;
+        self.shuffle_tiles(clock.unix_timestamp as u64)?;
 
-        // Add number tiles: 4 sets of 1-13 in 4 colors
-        for _ in 0..4 {
+        Ok(())
+    }
+
+    /// Fill the pool with the full, unshuffled tile set for this game
+    pub fn build_tile_pool(&mut self) {
+        let copies = if self.is_six_player() { 3 } else { 2 };
+        self.tile_pool = Vec::with_capacity(copies * 52 + self.rules.jokers as usize);
+
+        // Add number tiles: 2 sets of 1-13 in 4 colors (3 in the six-player edition)
+        for _ in 0..copies {
             for color in [
                 TileColor::Red,
                 TileColor::Blue,
@@ -47,28 +181,16 @@ impl GameState {
                 TileColor::Orange,
             ] {
                 for number in 1..=13 {
-                    self.tile_pool.push(Tile {
-                        tile_type: TileType::Number { color, number },
-                    });
+                    self.tile_pool.push(Tile::number(color, number));
                 }
             }
         }
 
-        // Add 2 jokers
-        self.tile_pool.push(Tile {
-            tile_type: TileType::Joker,
-        });
-        self.tile_pool.push(Tile {
-            tile_type: TileType::Joker,
-        });
-
-        self.tiles_remaining = TOTAL_TILES as u16;
-
-        // Shuffle tiles (simple pseudo-random based on clock)
-        let clock = Clock::get()?;
-        self.shuffle_tiles(clock.unix_timestamp as u64)?;
+        for _ in 0..self.rules.jokers {
+            self.tile_pool.push(Tile::JOKER);
+        }
 
-        Ok(())
+        self.tiles_remaining = self.tile_pool.len() as u16;
     }
 
     fn shuffle_tiles(&mut self, seed: u64) -> Result<()> {
@@ -108,8 +230,9 @@ impl GameState {
     }
 
     pub fn draw_initial_tiles(&mut self, player_index: usize) -> Result<()> {
+        let hand_size = self.rules.hand_size as usize;
         require!(
-            self.tiles_remaining >= TILES_PER_PLAYER as u16,
+            self.tiles_remaining >= hand_size as u16,
             crate::errors::RummikubError::NotEnoughTiles
         );
 
@@ -119,18 +242,153 @@ impl GameState {
         let seed = clock.unix_timestamp as u64 + (player_index as u64 * 1000);
         self.shuffle_remaining_tiles(seed)?;
 
-        for i in 0..TILES_PER_PLAYER {
+        self.deal_hand(player_index)
+    }
+
+    /// Move a full starting hand from the top of the pool to a rack
+    fn deal_hand(&mut self, player_index: usize) -> Result<()> {
+        for _ in 0..self.rules.hand_size {
             let tile = self
                 .tile_pool
                 .pop()
                 .ok_or(crate::errors::RummikubError::NotEnoughTiles)?;
-            self.players[player_index].tiles[i] = tile;
+            self.seat_mut(player_index).add_tile(tile);
             self.tiles_remaining -= 1;
         }
-        self.players[player_index].tile_count = TILES_PER_PLAYER as u8;
         Ok(())
     }
 
+    /// Clear a finished, paid-out game for another round with the same
+    /// settings: fresh pool, empty table, no players seated. Returns the
+    /// previous seats so they can be re-seated.
+    pub fn reset_for_rematch(&mut self, now: i64) -> Result<Vec<Player>> {
+        require!(
+            self.game_status == GameStatus::Finished && self.prize_pool == 0,
+            crate::errors::RummikubError::InvalidGameState
+        );
+
+        let previous: Vec<Player> = self.seated().cloned().collect();
+        self.players = Default::default();
+        self.extra_players.fill(Player::default());
+        self.current_players = 0;
+        self.current_turn = 0;
+        self.game_status = GameStatus::WaitingForPlayers;
+        self.winner = None;
+        self.table_melds.clear();
+        self.turn_number = 0;
+        self.last_committed_turn = 0;
+        self.last_activity = now;
+        self.finished_at = 0;
+        self.payout = 0;
+        self.lobby_page = None;
+        self.rounds_played = 0;
+
+        self.initialize_tile_pool()?;
+        Ok(previous)
+    }
+
+    /// Check that a player may take a seat: reserved players always can,
+    /// everyone else needs an unreserved seat, an invite and the password.
+    pub fn check_access(&self, player: &Pubkey, password: Option<&[u8]>) -> Result<()> {
+        let access = &self.access;
+        if access.reserved.contains(player) {
+            return Ok(());
+        }
+
+        let held = access
+            .reserved
+            .iter()
+            .filter(|r| !self.seated().any(|p| p.pubkey == **r))
+            .count();
+        require!(
+            (self.current_players as usize) + held < self.max_players as usize,
+            crate::errors::RummikubError::SeatsReserved
+        );
+        require!(
+            access.allowlist.is_empty() || access.allowlist.contains(player),
+            crate::errors::RummikubError::NotInvited
+        );
+        if let Some(expected) = access.password_hash {
+            let password = password.ok_or(crate::errors::RummikubError::InvalidPassword)?;
+            require!(
+                password_hash(self.game_id, password) == expected,
+                crate::errors::RummikubError::InvalidPassword
+            );
+        }
+        Ok(())
+    }
+
+    /// Unseat a player before the game starts, returning their tiles to the
+    /// pool. Later players move down a seat. Returns the stake to refund.
+    pub fn remove_player(&mut self, index: usize) -> Result<u64> {
+        require!(
+            self.game_status == GameStatus::WaitingForPlayers,
+            crate::errors::RummikubError::GameAlreadyStarted
+        );
+
+        let removed = std::mem::take(self.seat_mut(index));
+        let count = self.current_players as usize;
+        for i in index..count - 1 {
+            *self.seat_mut(i) = std::mem::take(self.seat_mut(i + 1));
+        }
+        self.current_players -= 1;
+
+        self.tile_pool.extend_from_slice(&removed.tiles);
+        self.tiles_remaining += removed.tile_count as u16;
+
+        let stake = self.entry_fee;
+        self.prize_pool -= stake;
+        Ok(stake)
+    }
+
+    /// Seat a player whose stake has already been collected, deal their hand
+    /// and start the game once the table is full. Returns the seat index.
+    pub fn add_player(&mut self, pubkey: Pubkey, stake: u64) -> Result<usize> {
+        require!(
+            self.game_status == GameStatus::WaitingForPlayers,
+            crate::errors::RummikubError::GameAlreadyStarted
+        );
+        require!(
+            self.current_players < self.max_players,
+            crate::errors::RummikubError::GameFull
+        );
+
+        // Add stake to prize pool
+        self.prize_pool += stake;
+
+        let player_index = self.current_players as usize;
+        *self.seat_mut(player_index) = Player {
+            pubkey,
+            tiles: Vec::new(),
+            tile_count: 0,
+            has_opened: false,
+            score: 0,
+            tiles_played: 0,
+            has_profile: false,
+            result_recorded: false,
+            rating: 0,
+            rating_delta: 0,
+            match_score: 0,
+        };
+
+        self.current_players += 1;
+
+        // Draw initial tiles for the player
+        self.draw_initial_tiles(player_index)?;
+
+        // Start game if all players joined
+        if self.current_players == self.max_players {
+            self.game_status = GameStatus::InProgress;
+            msg!(
+                "Game started with {} players. Prize pool: {} lamports",
+                self.current_players,
+                self.prize_pool
+            );
+        }
+
+        Ok(player_index)
+    }
+
     fn shuffle_remaining_tiles(&mut self, seed: u64) -> Result<()> {
         let mut rng = seed;
         let len = self.tile_pool.len();
@@ -152,10 +410,6 @@ impl GameState {
             self.tiles_remaining > 0,
             crate::errors::RummikubError::NotEnoughTiles
         );
-        require!(
-            self.players[player_index].tile_count < 21,
-            crate::errors::RummikubError::TooManyTiles
-        );
 
         // Add randomness before drawing from pool
         let clock = Clock::get()?;
@@ -168,15 +422,17 @@ impl GameState {
             .tile_pool
             .pop()
             .ok_or(crate::errors::RummikubError::NotEnoughTiles)?;
-        let count = self.players[player_index].tile_count as usize;
-        self.players[player_index].tiles[count] = tile;
-        self.players[player_index].tile_count += 1;
+        self.seat_mut(player_index).add_tile(tile);
         self.tiles_remaining -= 1;
 
         Ok(())
     }
 
     pub fn verify_turn(&self, player: &Pubkey) -> Result<()> {
+        require!(
+            !self.awaiting_deal(),
+            crate::errors::RummikubError::RoundNotDealt
+        );
         let player_index = self.get_player_index(player)?;
         require!(
             player_index == self.current_turn as usize,
@@ -188,12 +444,113 @@ impl GameState {
     pub fn get_player_index(&self, player: &Pubkey) -> Result<usize> {
         self.players
             .iter()
+            .chain(&self.extra_players)
             .position(|p| p.pubkey == *player)
             .ok_or(crate::errors::RummikubError::PlayerNotInGame.into())
     }
 
+    /// Seat `index`; seats past the fourth live in `extra_players`
+    pub fn seat(&self, index: usize) -> &Player {
+        match index.checked_sub(MAX_PLAYERS) {
+            Some(extra) => &self.extra_players[extra],
+            None => &self.players[index],
+        }
+    }
+
+    pub fn seat_mut(&mut self, index: usize) -> &mut Player {
+        match index.checked_sub(MAX_PLAYERS) {
+            Some(extra) => &mut self.extra_players[extra],
+            None => &mut self.players[index],
+        }
+    }
+
+    /// The seated players, in seat order
+    pub fn seated(&self) -> impl Iterator<Item = &Player> {
+        self.players
+            .iter()
+            .chain(&self.extra_players)
+            .take(self.current_players as usize)
+    }
+
+    pub fn seated_mut(&mut self) -> impl Iterator<Item = &mut Player> {
+        let seated = self.current_players as usize;
+        self.players
+            .iter_mut()
+            .chain(&mut self.extra_players)
+            .take(seated)
+    }
+
+    /// Partner seat in a team game
+    pub fn partner(&self, seat: usize) -> Option<usize> {
+        self.teams.then_some((seat + 2) % MAX_PLAYERS)
+    }
+
+    /// Whether the seat won, alone or as the winner's partner
+    pub fn is_winner(&self, seat: usize) -> bool {
+        let won = |i: usize| self.winner == Some(self.seat(i).pubkey);
+        won(seat) || self.partner(seat).is_some_and(won)
+    }
+
+    /// A seat's share of the payout: all of it for the winner, or half each
+    /// for a winning team, the odd lamport to the player who went out
+    pub fn winnings(&self, seat: usize) -> u64 {
+        if !self.is_winner(seat) {
+            return 0;
+        }
+        match self.partner(seat) {
+            Some(_) if self.winner == Some(self.seat(seat).pubkey) => self.payout - self.payout / 2,
+            Some(_) => self.payout / 2,
+            None => self.payout,
+        }
+    }
+
+    /// A seat's rating, or its team's average in team games
+    fn team_rating(&self, seat: usize) -> i32 {
+        let own = self.seat(seat).rating as i32;
+        match self.partner(seat) {
+            Some(partner) => (own + self.seat(partner).rating as i32) / 2,
+            None => own,
+        }
+    }
+
+    /// A seat's standing by `score`: its own, or its team's sum in team games
+    fn standing(&self, seat: usize, score: fn(&Player) -> i16) -> i16 {
+        let own = score(self.seat(seat));
+        match self.partner(seat) {
+            Some(partner) => own.saturating_add(score(self.seat(partner))),
+            None => own,
+        }
+    }
+
+    /// The game's authority or one of its seated players
+    pub fn is_participant(&self, key: &Pubkey) -> bool {
+        *key == self.authority || self.get_player_index(key).is_ok()
+    }
+
+    /// Whether more than half of the seated players are among `signers`
+    pub fn has_player_majority(&self, signers: &[Pubkey]) -> bool {
+        let approvals = self
+            .seated()
+            .filter(|p| signers.contains(&p.pubkey))
+            .count();
+        approvals * 2 > self.current_players as usize
+    }
+
     pub fn next_turn(&mut self) {
         self.current_turn = (self.current_turn + 1) % self.current_players;
+        self.turn_number += 1;
+    }
+
+    /// Whether the commit policy asks for a checkpoint after the turn that
+    /// just ended.
+    pub fn commit_due(&self, opened_meld: bool) -> bool {
+        match self.commit_policy {
+            CommitPolicy::Manual => false,
+            CommitPolicy::EveryNTurns { turns } => {
+                self.turn_number - self.last_committed_turn >= turns as u32
+            }
+            CommitPolicy::OnOpeningMeld => opened_meld,
+        }
     }
 
     pub fn execute_play(
@@ -208,34 +565,27 @@ impl GameState {
         // Verify player has all tiles they're trying to play
         for tile_play in &played_tiles {
             require!(
-                (tile_play.tile_index as usize) < self.players[player_index].tile_count as usize,
+                (tile_play.tile_index as usize) < self.seat(player_index).tile_count as usize,
                 crate::errors::RummikubError::InvalidTileIndex
             );
-            total_value +=
-                self.players[player_index].tiles[tile_play.tile_index as usize].get_value();
+            total_value += self.seat(player_index).tiles[tile_play.tile_index as usize]
+                .get_value(self.rules.joker_penalty);
         }
 
-        // Check initial meld requirement
-        let has_opened = self.players[player_index].has_opened;
+        // Check initial meld requirement. With partner melds, a player whose
+        // partner has opened may add tiles to the melds on the table; laying
+        // down melds of their own still needs the initial meld.
+        let partner_extension = !self.seat(player_index).has_opened
+            && self.partner_melds
+            && self
+                .partner(player_index)
+                .is_some_and(|p| self.seat(p).has_opened)
+            && !self.table_melds.is_empty()
+            && self.only_extends_table(&new_table_melds);
+        let has_opened = self.seat(player_index).has_opened || partner_extension;
         if !has_opened {
             require!(
-                total_value >= MIN_INITIAL_MELD as u16,
-                crate::errors::RummikubError::InitialMeldTooLow
-            );
-
-            // For initial meld, cannot rearrange table tiles - must use only hand tiles
-            require!(
-                self.table_melds.is_empty()
-                    || new_table_melds.len() == self.table_melds.len() + played_tiles.len(),
-                crate::errors::RummikubError::InitialMeldCannotUseTable
-            );
-        }
-
-        // Check initial meld requirement
-        let has_opened = self.players[player_index].has_opened;
-        if !has_opened {
-            require!(
-                total_value >= MIN_INITIAL_MELD as u16,
+                total_value >= self.rules.initial_meld as u16,
                 crate::errors::RummikubError::InitialMeldTooLow
             );
 
@@ -264,8 +614,12 @@ impl GameState {
         }
 
         // Validate all new table melds
+        require!(
+            new_table_melds.len() <= MAX_TABLE_MELDS,
+            crate::errors::RummikubError::TooManyMelds
+        );
         for meld in &new_table_melds {
-            meld.validate()?;
+            meld.validate(&self.rules)?;
         }
 
         // Remove played tiles from player's hand (in reverse order to maintain indices)
@@ -273,20 +627,46 @@ impl GameState {
         sorted_indices.sort_by(|a, b| b.cmp(a));
 
         for idx in sorted_indices {
-            self.players[player_index].remove_tile(idx as usize)?;
+            self.seat_mut(player_index).remove_tile(idx as usize)?;
         }
+        self.seat_mut(player_index).tiles_played += played_tiles.len() as u16;
 
         // Update table melds
         self.table_melds = new_table_melds;
 
-        // Mark player as opened
-        if !has_opened {
-            self.players[player_index].has_opened = true;
+        // Mark player as opened, unless they only played on their partner's
+        // opening
+        if !partner_extension {
+            self.seat_mut(player_index).has_opened = true;
         }
 
         Ok(())
     }
 
+    /// Whether `new_table_melds` only adds tiles to the melds on the table:
+    /// the same melds in the same order, each keeping all of its tiles
+    fn only_extends_table(&self, new_table_melds: &[Meld]) -> bool {
+        self.table_melds.len() == new_table_melds.len()
+            && self
+                .table_melds
+                .iter()
+                .zip(new_table_melds)
+                .all(|(old, new)| {
+                    let mut added = new.tiles.clone();
+                    old.meld_type == new.meld_type
+                        && old
+                            .tiles
+                            .iter()
+                            .all(|tile| match added.iter().position(|t| t == tile) {
+                                Some(i) => {
+                                    added.swap_remove(i);
+                                    true
+                                }
+                                None => false,
+                            })
+                })
+    }
+
     pub fn retrieve_joker(
         &mut self,
         player_index: usize,
@@ -294,7 +674,7 @@ impl GameState {
     ) -> Result<Tile> {
         // Must have opened (completed initial meld) to retrieve joker
         require!(
-            self.players[player_index].has_opened,
+            self.seat(player_index).has_opened,
             crate::errors::RummikubError::CannotRetrieveJokerBeforeOpening
         );
 
@@ -315,18 +695,18 @@ impl GameState {
         require!(
             self.table_melds[retrieval.meld_index as usize].tiles
                 [retrieval.joker_position as usize]
-                .tile_type
+                .tile_type()
                 == TileType::Joker,
             crate::errors::RummikubError::NotAJoker
         );
 
         // Validate replacement tile index
         require!(
-            (retrieval.replacement_tile as usize) < self.players[player_index].tile_count as usize,
+            (retrieval.replacement_tile as usize) < self.seat(player_index).tile_count as usize,
             crate::errors::RummikubError::InvalidTileIndex
         );
 
-        let replacement = self.players[player_index].tiles[retrieval.replacement_tile as usize];
+        let replacement = self.seat(player_index).tiles[retrieval.replacement_tile as usize];
 
         // Verify replacement tile is valid for this position (read-only check)
         self.verify_joker_replacement(
@@ -343,10 +723,11 @@ impl GameState {
         meld.tiles[retrieval.joker_position as usize] = replacement;
 
         // Validate the meld is still valid
-        meld.validate()?;
+        meld.validate(&self.rules)?;
 
         // Remove replacement tile from player's hand
-        self.players[player_index].remove_tile(retrieval.replacement_tile as usize)?;
+        self.seat_mut(player_index)
+            .remove_tile(retrieval.replacement_tile as usize)?;
 
         // Return the joker (caller must add it to hand and play it same turn)
         Ok(joker)
@@ -367,20 +748,17 @@ impl GameState {
 
                 for (i, tile) in meld.tiles.iter().enumerate() {
                     if i != joker_position as usize {
-                        match tile.tile_type {
-                            TileType::Number { color, number } => {
-                                if set_number.is_none() {
-                                    set_number = Some(number);
-                                }
-                                colors_used.push(color);
+                        if let TileType::Number { color, number } = tile.tile_type() {
+                            if set_number.is_none() {
+                                set_number = Some(number);
                             }
-                            _ => {}
+                            colors_used.push(color);
                         }
                     }
                 }
 
                 // Verify replacement has correct number and unique color
-                match replacement.tile_type {
+                match replacement.tile_type() {
                     TileType::Number { color, number } => {
                         if let Some(expected_number) = set_number {
                             require!(
@@ -401,24 +779,29 @@ impl GameState {
                 // Determine what number the joker represents
                 let mut run_color = None;
                 let mut expected_number = None;
+                let step = |number: u8, delta: i16| {
+                    let n = number as i16 + delta;
+                    if self.rules.wrap_runs {
+                        ((n - 1).rem_euclid(13) + 1) as u8
+                    } else {
+                        n as u8
+                    }
+                };
 
                 // Look at surrounding tiles to determine expected value
                 for (i, tile) in meld.tiles.iter().enumerate() {
-                    match tile.tile_type {
-                        TileType::Number { color, number } => {
-                            if run_color.is_none() {
-                                run_color = Some(color);
-                            }
+                    if let TileType::Number { color, number } = tile.tile_type() {
+                        if run_color.is_none() {
+                            run_color = Some(color);
+                        }
 
-                            // Calculate what number should be at joker position
-                            if i < joker_position as usize {
-                                let distance = (joker_position as i16) - (i as i16);
-                                expected_number = Some(number + distance as u8);
-                            } else if i == (joker_position as usize) + 1 {
-                                expected_number = Some(number - 1);
-                            }
+                        // Calculate what number should be at joker position
+                        if i < joker_position as usize {
+                            let distance = (joker_position as i16) - (i as i16);
+                            expected_number = Some(step(number, distance));
+                        } else if i == (joker_position as usize) + 1 {
+                            expected_number = Some(step(number, -1));
                         }
-                        _ => {}
                     }
                     if expected_number.is_some() {
                         break;
@@ -426,7 +809,7 @@ impl GameState {
                 }
 
                 // Verify replacement matches expected color and number
-                match replacement.tile_type {
+                match replacement.tile_type() {
                     TileType::Number { color, number } => {
                         require!(
                             Some(color) == run_color,
@@ -444,42 +827,741 @@ impl GameState {
         Ok(())
     }
 
-    pub fn end_game(&mut self, winner_index: usize) -> Result<()> {
-        self.game_status = GameStatus::Finished;
-        self.winner = Some(self.players[winner_index].pubkey);
+    /// Total value of the tiles left in a player's rack
+    pub fn hand_value(&self, player_index: usize) -> i16 {
+        let player = self.seat(player_index);
+        player
+            .tiles
+            .iter()
+            .map(|tile| tile.get_value(self.rules.joker_penalty) as i16)
+            .sum()
+    }
 
-        // Calculate scores
-        let mut total_opponent_tiles: i16 = 0;
+    /// Seat holding the lowest-value rack; ties go to the earlier seat
+    pub fn lowest_hand(&self) -> usize {
+        (0..self.current_players as usize)
+            .min_by_key(|&i| self.hand_value(i))
+            .unwrap_or(0)
+    }
 
-        for (i, player) in self.players.iter_mut().enumerate() {
-            if i != winner_index && i < self.current_players as usize {
-                let mut player_tiles_value: i16 = 0;
-                for j in 0..player.tile_count as usize {
-                    player_tiles_value += player.tiles[j].get_value() as i16;
+    /// Seat with the lowest rack among everyone but the player on turn, who
+    /// forfeits a stalled tournament table
+    pub fn lowest_hand_after_forfeit(&self) -> usize {
+        (0..self.current_players as usize)
+            .filter(|&i| i != self.current_turn as usize)
+            .min_by_key(|&i| self.hand_value(i))
+            .unwrap_or(0)
+    }
+
+    /// Pairwise Elo: every seat plays one match against every opponent,
+    /// decided by final (team) score, with K split across the opponents.
+    /// Team games rate each team at its partners' average, so both partners
+    /// move by the same amount.
+    pub fn compute_rating_deltas(&mut self) {
+        let seated = self.current_players as usize;
+        if seated < 2 {
+            return;
+        }
+
+        for i in 0..seated {
+            let mut total = 0i32; // Sum of (actual - expected), per mille
+            let mut opponents = 0i32;
+            for j in 0..seated {
+                if i == j || self.partner(i) == Some(j) {
+                    continue;
                 }
-                player.score = -player_tiles_value;
+                opponents += 1;
+                let mine = self.standing(i, |p| p.score);
+                let theirs = self.standing(j, |p| p.score);
+                let actual = match mine.cmp(&theirs) {
+                    std::cmp::Ordering::Greater => 1000,
+                    std::cmp::Ordering::Equal => 500,
+                    std::cmp::Ordering::Less => 0,
+                };
+                let lead = self.team_rating(i) - self.team_rating(j);
+                total += actual - elo_expected(lead);
+            }
+            self.seat_mut(i).rating_delta = (ELO_K * total / (1000 * opponents)) as i16;
+        }
+    }
+
+    /// Score the round `winner_index` went out in and add it to the match
+    /// totals. Returns the winner's round score.
+    fn score_round(&mut self, winner_index: usize) -> i16 {
+        let mut total_opponent_tiles: i16 = 0;
+
+        for i in 0..self.current_players as usize {
+            if i != winner_index {
+                let player_tiles_value = self.hand_value(i);
+                self.seat_mut(i).score = -player_tiles_value;
                 total_opponent_tiles += player_tiles_value;
             }
         }
 
-        self.players[winner_index].score = total_opponent_tiles;
+        self.seat_mut(winner_index).score = total_opponent_tiles;
 
-        msg!("Game ended. Winner score: {}", total_opponent_tiles);
+        if self.match_target.is_some() {
+            self.rounds_played += 1;
+            for player in self.seated_mut() {
+                player.match_score = player.match_score.saturating_add(player.score);
+            }
+        }
+        total_opponent_tiles
+    }
+
+    /// Whether a match has reached its target (always true for single games).
+    pub fn match_over(&self) -> bool {
+        match self.match_target {
+            None => true,
+            Some(MatchTarget::Rounds { rounds }) => self.rounds_played >= rounds,
+            Some(MatchTarget::Score { points }) => self.seated().any(|p| p.match_score >= points),
+        }
+    }
+
+    /// End the round `winner_index` went out in. A match that has not reached
+    /// its target clears the table for the next round and returns `None`;
+    /// otherwise the game finishes and the winning seat is returned.
+    pub fn end_round(&mut self, winner_index: usize) -> Result<Option<usize>> {
+        let round_score = self.score_round(winner_index);
+        if !self.match_over() {
+            self.clear_for_next_round();
+            msg!(
+                "Round {} won with {} points",
+                self.rounds_played,
+                round_score
+            );
+            return Ok(None);
+        }
+        self.finish(winner_index).map(Some)
+    }
+
+    /// Score the last round and finish the game, even a match that has not
+    /// reached its target. Returns the winning seat.
+    pub fn end_game(&mut self, winner_index: usize) -> Result<usize> {
+        self.score_round(winner_index);
+        self.finish(winner_index)
+    }
+
+    fn finish(&mut self, round_winner: usize) -> Result<usize> {
+        let mut winner_index = round_winner;
+        if self.match_target.is_some() {
+            // Highest (team) total wins; the last round's winner takes a tie
+            let seated = self.current_players as usize;
+            for i in 0..seated {
+                if self.standing(i, |p| p.match_score)
+                    > self.standing(winner_index, |p| p.match_score)
+                {
+                    winner_index = i;
+                }
+            }
+            // Report match totals from here on (profiles, ratings, events)
+            for player in self.seated_mut() {
+                player.score = player.match_score;
+            }
+        }
+
+        self.game_status = GameStatus::Finished;
+        self.winner = Some(self.seat(winner_index).pubkey);
+
+        // What either claim path pays out, before the partner split
+        let house_fee = (self.prize_pool as u128 * HOUSE_FEE_BPS as u128 / 10000) as u64;
+        self.payout = self.prize_pool - house_fee;
+
+        if self.rated {
+            self.compute_rating_deltas();
+        }
+
+        msg!(
+            "Game ended. Winner score: {}",
+            self.seat(winner_index).score
+        );
+        Ok(winner_index)
+    }
+
+    /// Clear the table and racks for the next round of a match and put a
+    /// fresh, unshuffled pool in place. The opening seat moves one place on
+    /// each round. Nobody can play until `deal_round_with_randomness` deals
+    /// it from VRF randomness, so no player can pick the next hands.
+    fn clear_for_next_round(&mut self) {
+        self.table_melds.clear();
+        for player in self.seated_mut() {
+            player.tiles.clear();
+            player.tile_count = 0;
+            player.has_opened = false;
+        }
+
+        self.build_tile_pool();
+        self.current_turn = self.rounds_played % self.current_players;
+        self.turn_number += 1;
+    }
+
+    /// Whether a match round has ended and the next one still waits to be
+    /// dealt (every rack is empty while the game runs)
+    pub fn awaiting_deal(&self) -> bool {
+        self.game_status == GameStatus::InProgress
+            && self.match_target.is_some()
+            && self.seated().all(|p| p.tile_count == 0)
+    }
+
+    /// Shuffle the fresh pool of a match's next round and deal every hand
+    pub fn deal_round_with_randomness(&mut self, randomness: [u8; 32]) -> Result<()> {
+        require!(
+            self.awaiting_deal(),
+            crate::errors::RummikubError::InvalidGameState
+        );
+        self.shuffle_tiles_with_randomness(randomness)?;
+        for i in 0..self.current_players as usize {
+            self.deal_hand(i)?;
+        }
         Ok(())
     }
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+/// Expected Elo score (per mille) for a rating lead, rounded to 25 points
+fn elo_expected(lead: i32) -> i32 {
+    let bucket = ((lead.abs().min(800) + 12) / 25) as usize;
+    let expected = ELO_EXPECTED_PERMILLE[bucket];
+    if lead >= 0 {
+        expected
+    } else {
+        1000 - expected
+    }
+}
+
+/// Holds a game's entry fees on base layer. Never delegated, so the money
+/// does not travel with the gameplay account into the Ephemeral Rollup.
+#[account]
+pub struct Escrow {
+    pub game_id: u64,
+    pub bump: u8,
+    pub settled: bool, // Paid out or moved by a stalled-game recovery
+}
+
+impl Escrow {
+    pub const SPACE: usize = 8 + // game_id
+        1 + // bump
+        1; // settled
+}
+
+/// Prepaid player balance. Can be delegated alongside games so entry fees are
+/// debited and winnings credited inside the Ephemeral Rollup.
+///
+/// Lamports held = rent + `balance` + `locked`, plus any house fees awaiting
+/// `sweep_bankroll_fees`.
+#[account]
+pub struct Bankroll {
+    pub owner: Pubkey,
+    pub balance: u64, // Available to join games or withdraw
+    pub locked: u64,  // Staked in games that have not been settled yet
+    pub bump: u8,
+}
+
+impl Bankroll {
+    pub const SPACE: usize = 32 + // owner
+        8 + // balance
+        8 + // locked
+        1; // bump
+}
+
+/// Ephemeral key allowed to take turns for one player in one game.
+/// Revoking closes the account.
+#[account]
+pub struct SessionKey {
+    pub game: Pubkey,
+    pub player: Pubkey,      // Wallet the session acts for
+    pub session_key: Pubkey, // Key that signs turns
+    pub expires_at: i64,
+    pub bump: u8,
+}
+
+impl SessionKey {
+    pub const SPACE: usize = 32 + // game
+        32 + // player
+        32 + // session_key
+        8 + // expires_at
+        1; // bump
+
+    pub fn is_valid(&self, game: &Pubkey, signer: &Pubkey, now: i64) -> Result<()> {
+        require!(
+            self.game == *game && self.session_key == *signer,
+            crate::errors::RummikubError::InvalidSessionKey
+        );
+        require!(
+            now < self.expires_at,
+            crate::errors::RummikubError::SessionExpired
+        );
+        Ok(())
+    }
+}
+
+/// Lifetime statistics of one wallet. Profiles stay on base layer; results
+/// of games played in the ER are written by `record_result` once the game is
+/// back on base layer, winnings by `claim_prize`.
+#[account]
+pub struct PlayerProfile {
+    pub owner: Pubkey,
+    pub display_name: String,
+    pub games_played: u32,
+    pub games_won: u32,
+    pub tiles_played: u64,
+    pub best_winning_score: i16,
+    pub lifetime_winnings: u64, // Prize shares of won games, added by record_result
+    pub rating: u16,
+    pub rated_games: u32,
+    pub season_id: u64,     // Season `season_points` belong to
+    pub season_points: u32, // Reset when a result lands in a new season
+    pub bump: u8,
+}
+
+impl PlayerProfile {
+    pub const SPACE: usize = 32 + // owner
+        4 + MAX_DISPLAY_NAME_LEN + // display_name
+        4 + // games_played
+        4 + // games_won
+        8 + // tiles_played
+        2 + // best_winning_score
+        8 + // lifetime_winnings
+        2 + // rating
+        4 + // rated_games
+        8 + // season_id
+        4 + // season_points
+        1; // bump
+
+    /// Fold one seat of a finished game into the lifetime counters
+    pub fn record(&mut self, player: &Player, won: bool, winnings: u64, rated: bool) {
+        self.games_played += 1;
+        if rated {
+            let rating = (self.rating as i32 + player.rating_delta as i32).max(MIN_RATING as i32);
+            self.rating = rating as u16;
+            self.rated_games += 1;
+        }
+        self.tiles_played += player.tiles_played as u64;
+        if won {
+            self.games_won += 1;
+            self.best_winning_score = self.best_winning_score.max(player.score);
+            self.lifetime_winnings += winnings;
+        }
+    }
+}
+
+/// A ranked period. Profiles earn points for results recorded with the
+/// season attached; the best `SEASON_LEADERBOARD_SIZE` are kept here, sorted,
+/// and share `reward_pool` by `payout_bps` when the season is finalized.
+#[account]
+pub struct Season {
+    pub season_id: u64,
+    pub start_ts: i64,
+    pub end_ts: i64,
+    pub reward_pool: u64, // Lamports held for the payout, above rent
+    pub finalized: bool,
+    pub bump: u8,
+    pub payout_bps: Vec<u16>,               // Share of the pool per rank
+    pub leaderboard: Vec<LeaderboardEntry>, // Highest points first
+}
+
+impl Season {
+    pub const SPACE: usize = 8 + // season_id
+        8 + // start_ts
+        8 + // end_ts
+        8 + // reward_pool
+        1 + // finalized
+        1 + // bump
+        4 + (2 * SEASON_LEADERBOARD_SIZE) + // payout_bps vec
+        4 + (LeaderboardEntry::SPACE * SEASON_LEADERBOARD_SIZE); // leaderboard vec
+
+    pub fn contains(&self, timestamp: i64) -> bool {
+        (self.start_ts..self.end_ts).contains(&timestamp)
+    }
+
+    /// Set a player's season total and keep the board sorted and bounded
+    pub fn submit(&mut self, player: Pubkey, points: u32) {
+        match self.leaderboard.iter_mut().find(|e| e.player == player) {
+            Some(entry) => entry.points = points,
+            None => self.leaderboard.push(LeaderboardEntry { player, points }),
+        }
+        // Stable sort: on equal points whoever got there first stays ahead
+        self.leaderboard.sort_by(|a, b| b.points.cmp(&a.points));
+        self.leaderboard.truncate(SEASON_LEADERBOARD_SIZE);
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub struct LeaderboardEntry {
+    pub player: Pubkey,
+    pub points: u32,
+}
+
+impl LeaderboardEntry {
+    pub const SPACE: usize = 32 + 4;
+}
+
+/// Buy-in tournament played over rounds of 2-4 player tables. Buy-ins are
+/// held in this account until `distribute_tournament_prizes`.
+#[account]
+pub struct Tournament {
+    pub tournament_id: u64,
+    pub authority: Pubkey, // Organizer; seats tables and pays their rent
+    pub format: TournamentFormat,
+    pub table_size: u8,
+    pub buy_in: u64,
+    pub max_entrants: u8,
+    pub status: TournamentStatus,
+    pub current_round: u8, // 0 until the first round is seated
+    pub prize_pool: u64,
+    pub bump: u8,
+    pub created_at: i64,        // Start of registration
+    pub payout_bps: Vec<u16>,   // Share of the pool per final standing
+    pub entrants: Vec<Entrant>, // Registration order
+    pub seating: Vec<u8>,       // Entrant indices of this round, table by table
+    pub tables: Vec<TournamentTable>,
+}
+
+impl Tournament {
+    pub const SPACE: usize = 8 + // tournament_id
+        32 + // authority
+        TournamentFormat::SPACE + // format
+        1 + // table_size
+        8 + // buy_in
+        1 + // max_entrants
+        1 + // status
+        1 + // current_round
+        8 + // prize_pool
+        1 + // bump
+        8 + // created_at
+        4 + (2 * MAX_TOURNAMENT_PAYOUTS) + // payout_bps vec
+        4 + (Entrant::SPACE * MAX_TOURNAMENT_ENTRANTS) + // entrants vec
+        4 + MAX_TOURNAMENT_ENTRANTS + // seating vec
+        4 + (TournamentTable::SPACE * MAX_TOURNAMENT_TABLES); // tables vec
+
+    /// Seat the next round: still-alive entrants in registration order for
+    /// single elimination, everyone by points for Swiss. Tables are filled as
+    /// evenly as possible; a table of one is a bye and wins immediately.
+    /// Table games get their ids when `create_table_game` creates them.
+    pub fn seat_round(&mut self) -> Result<()> {
+        let mut order: Vec<u8> = (0..self.entrants.len() as u8)
+            .filter(|&i| self.entrants[i as usize].eliminated_round == 0)
+            .collect();
+        if let TournamentFormat::Swiss { .. } = self.format {
+            // Stable sort keeps registration order between equal points
+            order.sort_by(|&a, &b| {
+                self.entrants[b as usize]
+                    .points
+                    .cmp(&self.entrants[a as usize].points)
+            });
+        }
+
+        let players = order.len();
+        require!(
+            players >= MIN_PLAYERS,
+            crate::errors::RummikubError::InvalidTournament
+        );
+        let table_count = players.div_ceil(self.table_size as usize);
+        let base = players / table_count;
+        let larger = players % table_count;
+
+        self.current_round += 1;
+        self.seating = order;
+        self.tables = Vec::with_capacity(table_count);
+        let mut first = 0u8;
+        for t in 0..table_count {
+            let size = (base + usize::from(t < larger)) as u8;
+            self.tables.push(TournamentTable {
+                game_id: 0,
+                first,
+                size,
+                winner: NO_WINNER,
+            });
+            if size == 1 {
+                self.apply_result(t, first);
+            }
+            first += size;
+        }
+        Ok(())
+    }
+
+    /// Record a table's winner (a position in `seating`). Swiss winners score
+    /// a point; single elimination knocks out everyone else at the table.
+    /// Finishes the tournament when this was the last table of the last round.
+    pub fn apply_result(&mut self, table_index: usize, winner: u8) {
+        let table = self.tables[table_index];
+        self.tables[table_index].winner = winner;
+
+        for position in table.first..table.first + table.size {
+            let index = self.seating[position as usize] as usize;
+            let entrant = &mut self.entrants[index];
+            match self.format {
+                TournamentFormat::Swiss { .. } if position == winner => entrant.points += 1,
+                TournamentFormat::SingleElimination if position != winner => {
+                    entrant.eliminated_round = self.current_round
+                }
+                _ => {}
+            }
+        }
+
+        if self.round_complete() && self.is_last_round() {
+            self.status = TournamentStatus::Finished;
+        }
+    }
+
+    pub fn round_complete(&self) -> bool {
+        self.tables.iter().all(|t| t.winner != NO_WINNER)
+    }
+
+    /// Whether a table game's result has reached the tournament. Games of
+    /// earlier rounds were all reported before the next round was seated.
+    pub fn table_reported(&self, game_id: u64) -> bool {
+        self.tables
+            .iter()
+            .find(|t| t.size > 1 && t.game_id == game_id)
+            .is_none_or(|t| t.winner != NO_WINNER)
+    }
+
+    fn is_last_round(&self) -> bool {
+        match self.format {
+            TournamentFormat::SingleElimination => {
+                self.entrants
+                    .iter()
+                    .filter(|e| e.eliminated_round == 0)
+                    .count()
+                    <= 1
+            }
+            TournamentFormat::Swiss { rounds } => self.current_round >= rounds,
+        }
+    }
+
+    /// Entrant indices from first place down
+    pub fn standings(&self) -> Vec<u8> {
+        let mut order: Vec<u8> = (0..self.entrants.len() as u8).collect();
+        match self.format {
+            TournamentFormat::SingleElimination => order.sort_by_key(|&i| {
+                // Still alive first, then whoever lasted longest
+                match self.entrants[i as usize].eliminated_round {
+                    0 => 0,
+                    round => u8::MAX - round + 1,
+                }
+            }),
+            TournamentFormat::Swiss { .. } => {
+                order.sort_by(|&a, &b| {
+                    self.entrants[b as usize]
+                        .points
+                        .cmp(&self.entrants[a as usize].points)
+                });
+            }
+        }
+        order
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum TournamentFormat {
+    SingleElimination,
+    Swiss { rounds: u8 },
+}
+
+impl TournamentFormat {
+    pub const SPACE: usize = 2; // 1 for enum + 1 for data
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum TournamentStatus {
+    Registration,
+    Running,
+    Finished,
+    Cancelled,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub struct Entrant {
+    pub player: Pubkey,
+    pub points: u16,          // Swiss: tables won (byes included)
+    pub eliminated_round: u8, // Single elimination: round knocked out, 0 = alive
+}
+
+impl Entrant {
+    pub const SPACE: usize = 32 + 2 + 1;
+}
+
+/// One table of the current round, covering `seating[first..first + size]`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub struct TournamentTable {
+    pub game_id: u64, // 0 for a bye or a game not created yet
+    pub first: u8,
+    pub size: u8,
+    pub winner: u8, // Position in `seating`, NO_WINNER until reported
+}
+
+impl TournamentTable {
+    pub const SPACE: usize = 8 + 1 + 1 + 1;
+}
+
+/// Players waiting for a table at one stake and table size. Stakes are held
+/// here until `match_players` moves them into the new game's escrow.
+#[account]
+pub struct MatchQueue {
+    pub stake: u64,
+    pub player_count: u8,
+    pub rating_band: u16, // Max rating spread at one table, 0 = first come first served
+    pub bump: u8,
+    pub waiting: Vec<QueueEntry>, // Oldest first
+}
+
+impl MatchQueue {
+    pub const SPACE: usize = 8 + // stake
+        1 + // player_count
+        2 + // rating_band
+        1 + // bump
+        4 + (QueueEntry::SPACE * MAX_QUEUE_SIZE); // waiting vec
+
+    /// Queue positions of the next table to seat: the oldest player together
+    /// with the oldest players that keep the whole table within
+    /// `rating_band` (highest minus lowest rating), trying each player in
+    /// turn as the anchor.
+    pub fn pick_group(&self) -> Option<Vec<usize>> {
+        let needed = self.player_count as usize;
+        for anchor in 0..self.waiting.len() {
+            let mut group = vec![anchor];
+            let mut low = self.waiting[anchor].rating;
+            let mut high = low;
+            for i in anchor + 1..self.waiting.len() {
+                if group.len() == needed {
+                    break;
+                }
+                let rating = self.waiting[i].rating;
+                if self.rating_band == 0 || high.max(rating) - low.min(rating) <= self.rating_band {
+                    group.push(i);
+                    low = low.min(rating);
+                    high = high.max(rating);
+                }
+            }
+            if group.len() == needed {
+                return Some(group);
+            }
+        }
+        None
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub struct QueueEntry {
+    pub player: Pubkey,
+    pub rating: u16, // From the player's profile, INITIAL_RATING without one
+    pub has_profile: bool,
+}
+
+impl QueueEntry {
+    pub const SPACE: usize = 32 + 2 + 1;
+}
+
+/// One page of the open-game registry. Clients read the pages instead of
+/// scanning every game account to render a lobby.
+#[account]
+pub struct LobbyPage {
+    pub page: u32,
+    pub bump: u8,
+    pub entries: Vec<LobbyEntry>, // Oldest first
+}
+
+impl LobbyPage {
+    pub const SPACE: usize = 4 + // page
+        1 + // bump
+        4 + (LobbyEntry::SPACE * LOBBY_PAGE_SIZE); // entries vec
+
+    pub fn list(&mut self, entry: LobbyEntry) -> Result<()> {
+        require!(
+            self.entries.len() < LOBBY_PAGE_SIZE,
+            crate::errors::RummikubError::LobbyPageFull
+        );
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    /// Refresh a listed game's open seats, dropping it once none are left.
+    pub fn update(&mut self, game_id: u64, seats_left: u8) {
+        if seats_left == 0 {
+            self.remove(game_id);
+        } else if let Some(entry) = self.entries.iter_mut().find(|e| e.game_id == game_id) {
+            entry.seats_left = seats_left;
+        }
+    }
+
+    pub fn remove(&mut self, game_id: u64) {
+        self.entries.retain(|e| e.game_id != game_id);
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub struct LobbyEntry {
+    pub game_id: u64,
+    pub stake: u64,
+    pub seats_left: u8,
+    pub options: GameOptions, // Rules variant
+    pub private: bool,        // Allowlist or password required
+    pub created_at: i64,
+}
+
+impl LobbyEntry {
+    pub const SPACE: usize = 8 + 8 + 1 + GameOptions::SPACE + 1 + 8;
+}
+
+/// Hands out game ids so concurrent creators never race for the same PDA.
+#[account]
+pub struct GameCounter {
+    pub next_game_id: u64,
+    pub bump: u8,
+}
+
+impl GameCounter {
+    pub const SPACE: usize = 8 + // next_game_id
+        1; // bump
+}
+
+/// Program-wide settings controlled by the admin.
+#[account]
+pub struct Config {
+    pub admin: Pubkey,
+    pub paused: bool,      // Blocks new games and joins
+    pub play_paused: bool, // Also blocks turns in running games
+    pub bump: u8,
+    pub allowed_validators: Vec<Pubkey>, // ER validators games may be delegated to
+    pub recovery_timeout: i64,           // Seconds before a stalled game can be recovered
+    pub active_season: u64,              // Season receiving fees, 0 = none
+    pub season_fee_bps: u16,             // Share of each house fee routed to it
+}
+
+impl Config {
+    pub const SPACE: usize = 32 + // admin
+        1 +  // paused
+        1 +  // play_paused
+        1 +  // bump
+        4 + (32 * MAX_VALIDATORS) + // allowed_validators vec
+        8 + // recovery_timeout
+        8 + // active_season
+        2; // season_fee_bps
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
 pub struct Player {
     pub pubkey: Pubkey,
-    pub tiles: [Tile; 21], // Allow up to 21 tiles (14 starting + potential draws)
-    pub tile_count: u8,
+    pub tiles: Vec<Tile>, // Rack; grows with every draw
+    pub tile_count: u8,   // Always tiles.len()
     pub has_opened: bool,
     pub score: i16,
+    pub tiles_played: u16,
+    pub has_profile: bool,     // Joined with a PlayerProfile attached
+    pub result_recorded: bool, // Result written to the profile by record_result
+    pub rating: u16,           // Profile rating when joining a rated game
+    pub rating_delta: i16,     // Set by end_game in rated games
+    pub match_score: i16,      // Sum of round scores in a match
 }
 
 impl Player {
-    pub const SPACE: usize = 32 + (Tile::SPACE * 21) + 1 + 1 + 2;
+    // The rack's tiles are counted in `GameState::SPACE`'s tile total
+    pub const SPACE: usize = 32 + 4 + 1 + 1 + 2 + 2 + 1 + 1 + 2 + 2 + 2;
+
+    pub fn add_tile(&mut self, tile: Tile) {
+        self.tiles.push(tile);
+        self.tile_count += 1;
+    }
 
     pub fn remove_tile(&mut self, index: usize) -> Result<()> {
         require!(
@@ -487,27 +1569,46 @@ impl Player {
             crate::errors::RummikubError::InvalidTileIndex
         );
 
-        // Shift tiles down
-        for i in index..((self.tile_count as usize) - 1) {
-            self.tiles[i] = self.tiles[i + 1];
-        }
+        self.tiles.remove(index);
         self.tile_count -= 1;
         Ok(())
     }
 }
 
+/// A tile in one byte: bit 7 flags a joker, bits 4-5 hold the color and
+/// bits 0-3 the number. 0 is an empty slot; any other code decodes as empty.
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq)]
 pub struct Tile {
-    pub tile_type: TileType,
+    pub code: u8,
 }
 
 impl Tile {
-    pub const SPACE: usize = 3; // 1 for enum + 2 for data
+    pub const SPACE: usize = 1;
+    pub const JOKER: Tile = Tile { code: 0x80 };
 
-    pub fn get_value(&self) -> u16 {
-        match self.tile_type {
+    pub fn number(color: TileColor, number: u8) -> Tile {
+        Tile {
+            code: (color as u8) << 4 | (number & 0x0f),
+        }
+    }
+
+    pub fn tile_type(&self) -> TileType {
+        let number = self.code & 0x0f;
+        match self.code {
+            0x80 => TileType::Joker,
+            code if code & 0xc0 == 0 && (1..=13).contains(&number) => TileType::Number {
+                color: TileColor::from_bits(code >> 4),
+                number,
+            },
+            _ => TileType::Empty,
+        }
+    }
+
+    /// Face value, with jokers worth `joker_value`
+    pub fn get_value(&self, joker_value: u8) -> u16 {
+        match self.tile_type() {
             TileType::Number { number, .. } => number as u16,
-            TileType::Joker => JOKER_SCORE as u16,
+            TileType::Joker => joker_value as u16,
             TileType::Empty => 0,
         }
     }
@@ -532,6 +1633,17 @@ pub enum TileColor {
     Orange,
 }
 
+impl TileColor {
+    fn from_bits(bits: u8) -> TileColor {
+        match bits & 0x03 {
+            0 => TileColor::Red,
+            1 => TileColor::Blue,
+            2 => TileColor::Black,
+            _ => TileColor::Orange,
+        }
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct Meld {
     pub meld_type: MeldType,
@@ -539,16 +1651,21 @@ pub struct Meld {
 }
 
 impl Meld {
-    pub const SPACE: usize = 1 + 4 + (Tile::SPACE * 13); // type + vec + max 13 tiles
+    // type + vec; the tiles are counted in `GameState::SPACE`'s tile total
+    pub const SPACE: usize = 1 + 4;
 
-    pub fn validate(&self) -> Result<()> {
+    pub fn validate(&self, rules: &GameRules) -> Result<()> {
         let tile_count = self.tiles.len();
 
         require!(tile_count >= 3, crate::errors::RummikubError::MeldTooSmall);
+        require!(
+            tile_count <= MAX_MELD_TILES,
+            crate::errors::RummikubError::MeldTooLarge
+        );
 
         match self.meld_type {
             MeldType::Set => self.validate_set(),
-            MeldType::Run => self.validate_run(),
+            MeldType::Run => self.validate_run(rules.wrap_runs),
         }
     }
 
@@ -563,7 +1680,7 @@ impl Meld {
         let mut joker_count = 0u8;
 
         for tile in &self.tiles {
-            match tile.tile_type {
+            match tile.tile_type() {
                 TileType::Number { color, number: n } => {
                     // All tiles must have same number
                     if let Some(expected_num) = number {
@@ -595,7 +1712,7 @@ impl Meld {
         // Total distinct colors (real + jokers) must be 3 or 4
         let total_colors = colors_used.len() + joker_count as usize;
         require!(
-            total_colors >= 3 && total_colors <= 4,
+            (3..=4).contains(&total_colors),
             crate::errors::RummikubError::InvalidSet
         );
 
@@ -609,7 +1726,7 @@ impl Meld {
         Ok(())
     }
 
-    fn validate_run(&self) -> Result<()> {
+    fn validate_run(&self, wrap: bool) -> Result<()> {
         require!(
             self.tiles.len() >= 3,
             crate::errors::RummikubError::InvalidRun
@@ -621,7 +1738,7 @@ impl Meld {
 
         // First pass: collect all numbers and jokers, verify color consistency
         for tile in &self.tiles {
-            match tile.tile_type {
+            match tile.tile_type() {
                 TileType::Number {
                     color: c,
                     number: n,
@@ -637,7 +1754,10 @@ impl Meld {
                     }
 
                     // Verify number is in valid range (1-13)
-                    require!(n >= 1 && n <= 13, crate::errors::RummikubError::InvalidRun);
+                    require!(
+                        (1..=13).contains(&n),
+                        crate::errors::RummikubError::InvalidRun
+                    );
                     numbers_in_run.push(n);
                 }
                 TileType::Joker => {
@@ -672,7 +1792,18 @@ impl Meld {
 
         let min_number = numbers_in_run[0];
         let max_number = numbers_in_run[numbers_in_run.len() - 1];
-        let span = max_number - min_number + 1;
+        let mut span = max_number - min_number + 1;
+        if wrap {
+            // The run may continue from 13 to 1; it covers everything
+            // except the widest gap between neighbouring numbers
+            let widest_gap = numbers_in_run
+                .windows(2)
+                .map(|pair| pair[1] - pair[0])
+                .chain([min_number + 13 - max_number])
+                .max()
+                .unwrap_or(13);
+            span = 14 - widest_gap;
+        }
         let gaps = (span as usize) - numbers_in_run.len();
 
         // Verify we have exactly enough jokers to fill the gaps
@@ -709,9 +1840,152 @@ pub struct JokerRetrieval {
     pub replacement_tile: u8, // Tile index from hand to replace joker
 }
 
+/// When a delegated game checkpoints itself to base layer, on top of the
+/// validator's own `commit_frequency_ms`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Default)]
+pub enum CommitPolicy {
+    #[default]
+    Manual,
+    EveryNTurns {
+        turns: u16,
+    },
+    OnOpeningMeld,
+}
+
+impl CommitPolicy {
+    pub const SPACE: usize = 3; // 1 for enum + 2 for data
+}
+
+/// Restrictions on who may join a game, set at `initialize_game`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Default)]
+pub struct GameAccess {
+    pub allowlist: Vec<Pubkey>,          // Empty = anyone may join
+    pub password_hash: Option<[u8; 32]>, // See `password_hash`
+    pub reserved: Vec<Pubkey>,           // Seats held for invited players
+}
+
+impl GameAccess {
+    pub const SPACE: usize = 4 + (32 * MAX_ALLOWLIST) + // allowlist
+        33 + // password_hash
+        4 + (32 * MAX_PLAYERS); // reserved
+
+    pub fn validate(&self, max_players: u8) -> Result<()> {
+        require!(
+            self.allowlist.len() <= MAX_ALLOWLIST && self.reserved.len() <= max_players as usize,
+            crate::errors::RummikubError::TooManyInvites
+        );
+        Ok(())
+    }
+
+    pub fn is_private(&self) -> bool {
+        !self.allowlist.is_empty() || self.password_hash.is_some()
+    }
+}
+
+/// Hash a game password is stored as. Salted with the game id so the same
+/// password hashes differently in every game.
+pub fn password_hash(game_id: u64, password: &[u8]) -> [u8; 32] {
+    solana_sha256_hasher::hashv(&[&game_id.to_le_bytes(), password]).to_bytes()
+}
+
+/// Per-game settings chosen at `initialize_game`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Default)]
+pub struct GameOptions {
+    pub rated: bool, // Every player must join with a profile; ratings change
+    pub match_target: Option<MatchTarget>, // Play a multi-round match
+    pub teams: bool, // 2v2: seats 0 & 2 against 1 & 3
+    pub partner_melds: bool, // Teams only: play onto the table once your partner has opened
+    pub rules: GameRules, // House rules; `Default` is the standard game
+}
+
+impl GameOptions {
+    pub const SPACE: usize = 1 + // rated
+        1 + MatchTarget::SPACE + // match_target
+        1 + // teams
+        1 + // partner_melds
+        GameRules::SPACE; // rules
+
+    /// Standard options for a game of `max_players`, with the six-player
+    /// edition's jokers when it has more than four seats
+    pub fn for_players(max_players: u8) -> Self {
+        let mut options = GameOptions::default();
+        if max_players as usize > MAX_PLAYERS {
+            options.rules.jokers = SIX_PLAYER_JOKERS;
+        }
+        options
+    }
+}
+
+/// Rule variant a game is played under.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub struct GameRules {
+    pub initial_meld: u8,  // Points needed for the first play
+    pub hand_size: u8,     // Tiles dealt to each player
+    pub jokers: u8,        // Jokers in the pool
+    pub joker_penalty: u8, // Value of a joker, in a rack or an initial meld
+    pub wrap_runs: bool,   // Runs may continue from 13 to 1
+}
+
+impl GameRules {
+    pub const SPACE: usize = 1 + 1 + 1 + 1 + 1;
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.initial_meld <= MAX_INITIAL_MELD
+                && (MIN_HAND_SIZE..=MAX_HAND_SIZE).contains(&self.hand_size)
+                && self.jokers as usize <= MAX_JOKERS
+                && self.joker_penalty <= MAX_JOKER_PENALTY,
+            crate::errors::RummikubError::InvalidRules
+        );
+        Ok(())
+    }
+}
+
+impl Default for GameRules {
+    fn default() -> Self {
+        GameRules {
+            initial_meld: MIN_INITIAL_MELD,
+            hand_size: TILES_PER_PLAYER as u8,
+            jokers: 2,
+            joker_penalty: JOKER_SCORE as u8,
+            wrap_runs: false,
+        }
+    }
+}
+
+/// When a multi-round match ends. The prize goes to the highest cumulative
+/// score once it does.
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum MatchTarget {
+    Rounds { rounds: u8 }, // After this many rounds
+    Score { points: i16 }, // Once a player's total reaches this
+}
+
+impl MatchTarget {
+    pub const SPACE: usize = 1 + 2;
+
+    pub fn validate(&self) -> Result<()> {
+        let valid = match *self {
+            MatchTarget::Rounds { rounds } => rounds > 0 && rounds <= MAX_MATCH_ROUNDS,
+            MatchTarget::Score { points } => points > 0,
+        };
+        require!(valid, crate::errors::RummikubError::InvalidMatchTarget);
+        Ok(())
+    }
+}
+
+/// How `settle_stalled_game` pays out a game stuck in a stalled rollup.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum RecoveryMode {
+    Refund,     // Every seated player gets their entry fee back
+    ScoreHands, // Lowest rack in the snapshot wins the pot
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Default)]
 pub enum GameStatus {
+    #[default]
     WaitingForPlayers,
     InProgress,
     Finished,
+    Cancelled,
 }
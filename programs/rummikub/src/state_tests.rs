@@ -1,19 +1,44 @@
 #[cfg(test)]
 mod tests {
-    use crate::state::{Meld, MeldType, Tile, TileColor, TileType};
+    use crate::constants::{
+        ENTRY_FEE_LAMPORTS, GAME_DISCRIMINATOR, GAME_VERSION, LEGACY_GAME_DISCRIMINATOR,
+        MAX_JOKERS, MAX_MELD_TILES, MAX_PLAYERS, MAX_TABLE_MELDS, SIX_PLAYER_JOKERS, TOTAL_TILES,
+    };
+    use crate::legacy::{
+        game_version, load_game, GameStateV0, MeldV0, PlayerV0, TileV0, RACK_SLOTS_V0,
+    };
+    use crate::state::{
+        password_hash, CommitPolicy, Entrant, GameAccess, GameOptions, GameRules, GameState,
+        GameStatus, LobbyEntry, LobbyPage, MatchQueue, MatchTarget, Meld, MeldType, Player,
+        PlayerProfile, QueueEntry, Season, SessionKey, Tile, TileColor, TilePlay, TileType,
+        Tournament, TournamentFormat, TournamentStatus,
+    };
+    use anchor_lang::prelude::{AnchorSerialize, Pubkey};
+    use anchor_lang::Discriminator;
 
     // Helper function to create a number tile
     fn number_tile(color: TileColor, number: u8) -> Tile {
-        Tile {
-            tile_type: TileType::Number { color, number },
-        }
+        Tile::number(color, number)
     }
 
     // Helper function to create a joker tile
     fn joker_tile() -> Tile {
-        Tile {
-            tile_type: TileType::Joker,
+        Tile::JOKER
+    }
+
+    // Helper function to create a game with the given players seated
+    fn game_with_players(players: &[Pubkey]) -> GameState {
+        let mut game = GameState {
+            authority: Pubkey::new_unique(),
+            max_players: players.len() as u8,
+            current_players: players.len() as u8,
+            extra_players: vec![Player::default(); players.len().saturating_sub(MAX_PLAYERS)],
+            ..Default::default()
+        };
+        for (i, pubkey) in players.iter().enumerate() {
+            game.seat_mut(i).pubkey = *pubkey;
         }
+        game
     }
 
     #[test]
@@ -26,7 +51,7 @@ mod tests {
                 number_tile(TileColor::Black, 7),
             ],
         };
-        assert!(meld.validate().is_ok());
+        assert!(meld.validate(&GameRules::default()).is_ok());
     }
 
     #[test]
@@ -40,7 +65,7 @@ mod tests {
                 number_tile(TileColor::Orange, 10),
             ],
         };
-        assert!(meld.validate().is_ok());
+        assert!(meld.validate(&GameRules::default()).is_ok());
     }
 
     #[test]
@@ -53,7 +78,7 @@ mod tests {
                 joker_tile(),
             ],
         };
-        assert!(meld.validate().is_ok());
+        assert!(meld.validate(&GameRules::default()).is_ok());
     }
 
     #[test]
@@ -62,7 +87,7 @@ mod tests {
             meld_type: MeldType::Set,
             tiles: vec![number_tile(TileColor::Red, 8), joker_tile(), joker_tile()],
         };
-        assert!(meld.validate().is_ok());
+        assert!(meld.validate(&GameRules::default()).is_ok());
     }
 
     #[test]
@@ -76,7 +101,7 @@ mod tests {
                 joker_tile(),
             ],
         };
-        assert!(meld.validate().is_ok());
+        assert!(meld.validate(&GameRules::default()).is_ok());
     }
 
     #[test]
@@ -85,7 +110,7 @@ mod tests {
             meld_type: MeldType::Set,
             tiles: vec![joker_tile(), joker_tile(), joker_tile()],
         };
-        assert!(meld.validate().is_err());
+        assert!(meld.validate(&GameRules::default()).is_err());
     }
 
     #[test]
@@ -98,7 +123,7 @@ mod tests {
                 number_tile(TileColor::Blue, 9),
             ],
         };
-        assert!(meld.validate().is_err());
+        assert!(meld.validate(&GameRules::default()).is_err());
     }
 
     #[test]
@@ -111,7 +136,7 @@ mod tests {
                 number_tile(TileColor::Black, 7),
             ],
         };
-        assert!(meld.validate().is_err());
+        assert!(meld.validate(&GameRules::default()).is_err());
     }
 
     #[test]
@@ -126,7 +151,7 @@ mod tests {
                 joker_tile(),
             ],
         };
-        assert!(meld.validate().is_err());
+        assert!(meld.validate(&GameRules::default()).is_err());
     }
 
     #[test]
@@ -139,7 +164,7 @@ mod tests {
                 number_tile(TileColor::Red, 7),
             ],
         };
-        assert!(meld.validate().is_ok());
+        assert!(meld.validate(&GameRules::default()).is_ok());
     }
 
     #[test]
@@ -154,7 +179,7 @@ mod tests {
                 number_tile(TileColor::Blue, 5),
             ],
         };
-        assert!(meld.validate().is_ok());
+        assert!(meld.validate(&GameRules::default()).is_ok());
     }
 
     #[test]
@@ -170,7 +195,7 @@ mod tests {
                 number_tile(TileColor::Red, 4),
             ],
         };
-        assert!(meld.validate().is_ok());
+        assert!(meld.validate(&GameRules::default()).is_ok());
     }
 
     #[test]
@@ -183,7 +208,7 @@ mod tests {
                 number_tile(TileColor::Blue, 7),
             ],
         };
-        assert!(meld.validate().is_ok());
+        assert!(meld.validate(&GameRules::default()).is_ok());
     }
 
     #[test]
@@ -199,7 +224,7 @@ mod tests {
                 number_tile(TileColor::Black, 11),
             ],
         };
-        assert!(meld.validate().is_ok());
+        assert!(meld.validate(&GameRules::default()).is_ok());
     }
 
     #[test]
@@ -213,7 +238,7 @@ mod tests {
                 number_tile(TileColor::Orange, 4),
             ],
         };
-        assert!(meld.validate().is_ok());
+        assert!(meld.validate(&GameRules::default()).is_ok());
     }
 
     #[test]
@@ -222,7 +247,7 @@ mod tests {
             meld_type: MeldType::Run,
             tiles: vec![joker_tile(), joker_tile(), joker_tile()],
         };
-        assert!(meld.validate().is_err());
+        assert!(meld.validate(&GameRules::default()).is_err());
     }
 
     #[test]
@@ -235,7 +260,7 @@ mod tests {
                 number_tile(TileColor::Red, 7),
             ],
         };
-        assert!(meld.validate().is_err());
+        assert!(meld.validate(&GameRules::default()).is_err());
     }
 
     #[test]
@@ -248,7 +273,7 @@ mod tests {
                 number_tile(TileColor::Red, 4),
             ],
         };
-        assert!(meld.validate().is_err());
+        assert!(meld.validate(&GameRules::default()).is_err());
     }
 
     #[test]
@@ -261,7 +286,7 @@ mod tests {
                 number_tile(TileColor::Blue, 5),
             ],
         };
-        assert!(meld.validate().is_err());
+        assert!(meld.validate(&GameRules::default()).is_err());
     }
 
     #[test]
@@ -278,7 +303,7 @@ mod tests {
             ],
         };
         // Actually this should fail because gaps (0) != jokers (1)
-        assert!(meld.validate().is_err());
+        assert!(meld.validate(&GameRules::default()).is_err());
     }
 
     #[test]
@@ -291,7 +316,7 @@ mod tests {
                 number_tile(TileColor::Blue, 6),
             ],
         };
-        assert!(meld.validate().is_err());
+        assert!(meld.validate(&GameRules::default()).is_err());
     }
 
     #[test]
@@ -306,7 +331,7 @@ mod tests {
             ],
         };
         // This should fail because span would be 13-1+1 = 13, which is >= 13
-        assert!(meld.validate().is_err());
+        assert!(meld.validate(&GameRules::default()).is_err());
     }
 
     #[test]
@@ -329,7 +354,7 @@ mod tests {
                 number_tile(TileColor::Red, 13),
             ],
         };
-        assert!(meld.validate().is_ok());
+        assert!(meld.validate(&GameRules::default()).is_ok());
     }
 
     #[test]
@@ -341,6 +366,1132 @@ mod tests {
                 number_tile(TileColor::Blue, 5),
             ],
         };
-        assert!(meld.validate().is_err());
+        assert!(meld.validate(&GameRules::default()).is_err());
+    }
+
+    #[test]
+    fn test_run_wraps_only_when_allowed() {
+        let wrapping = GameRules {
+            wrap_runs: true,
+            ..Default::default()
+        };
+        let meld = Meld {
+            meld_type: MeldType::Run,
+            tiles: vec![
+                number_tile(TileColor::Red, 12),
+                number_tile(TileColor::Red, 13),
+                number_tile(TileColor::Red, 1),
+            ],
+        };
+        assert!(meld.validate(&GameRules::default()).is_err());
+        assert!(meld.validate(&wrapping).is_ok());
+
+        // A joker can stand in for the 1 between 13 and 2
+        let meld = Meld {
+            meld_type: MeldType::Run,
+            tiles: vec![
+                number_tile(TileColor::Blue, 13),
+                joker_tile(),
+                number_tile(TileColor::Blue, 2),
+            ],
+        };
+        assert!(meld.validate(&wrapping).is_ok());
+
+        // Wrapping does not allow gaps elsewhere
+        let meld = Meld {
+            meld_type: MeldType::Run,
+            tiles: vec![
+                number_tile(TileColor::Blue, 13),
+                number_tile(TileColor::Blue, 1),
+                number_tile(TileColor::Blue, 3),
+            ],
+        };
+        assert!(meld.validate(&wrapping).is_err());
+    }
+
+    #[test]
+    fn test_game_rules_bounds() {
+        assert!(GameRules::default().validate().is_ok());
+        for rules in [
+            GameRules {
+                initial_meld: 51,
+                ..Default::default()
+            },
+            GameRules {
+                hand_size: 6,
+                ..Default::default()
+            },
+            GameRules {
+                hand_size: 17,
+                ..Default::default()
+            },
+            GameRules {
+                jokers: 5,
+                ..Default::default()
+            },
+            GameRules {
+                joker_penalty: 51,
+                ..Default::default()
+            },
+        ] {
+            assert!(rules.validate().is_err());
+        }
+    }
+
+    #[test]
+    fn test_rules_opening_and_joker_penalty() {
+        let players = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let mut game = game_with_players(&players);
+        game.rules = GameRules {
+            initial_meld: 0,
+            joker_penalty: 50,
+            ..Default::default()
+        };
+        for color in [TileColor::Red, TileColor::Blue, TileColor::Black] {
+            game.players[0].add_tile(number_tile(color, 1));
+        }
+        game.players[1].add_tile(joker_tile());
+        game.players[1].add_tile(number_tile(TileColor::Red, 4));
+
+        // No opening threshold: three 1s are enough
+        let melds = vec![Meld {
+            meld_type: MeldType::Set,
+            tiles: game.players[0].tiles[..3].to_vec(),
+        }];
+        game.execute_play(
+            0,
+            (0..3).map(|i| TilePlay { tile_index: i }).collect(),
+            melds,
+        )
+        .unwrap();
+
+        game.end_game(0).unwrap();
+        assert_eq!(game.players[1].score, -54);
+        assert_eq!(game.players[0].score, 54);
+    }
+
+    #[test]
+    fn test_player_majority() {
+        let players = [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+        let game = game_with_players(&players);
+
+        assert!(!game.has_player_majority(&[players[0]]));
+        assert!(game.has_player_majority(&[players[0], players[2]]));
+        // Duplicate signers only count once
+        assert!(!game.has_player_majority(&[players[1], players[1]]));
+        // Outsiders and the authority do not count
+        assert!(!game.has_player_majority(&[players[1], game.authority, Pubkey::new_unique()]));
+    }
+
+    #[test]
+    fn test_is_participant() {
+        let players = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let game = game_with_players(&players);
+
+        assert!(game.is_participant(&game.authority));
+        assert!(game.is_participant(&players[1]));
+        assert!(!game.is_participant(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_commit_policy_due() {
+        let players = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let mut game = game_with_players(&players);
+
+        // Manual never commits on its own
+        game.next_turn();
+        assert!(!game.commit_due(true));
+
+        game.commit_policy = CommitPolicy::EveryNTurns { turns: 2 };
+        game.last_committed_turn = game.turn_number;
+        game.next_turn();
+        assert!(!game.commit_due(false));
+        game.next_turn();
+        assert!(game.commit_due(false));
+
+        game.commit_policy = CommitPolicy::OnOpeningMeld;
+        assert!(!game.commit_due(false));
+        assert!(game.commit_due(true));
+    }
+
+    #[test]
+    fn test_session_key_validity() {
+        let game = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let session = SessionKey {
+            game,
+            player: Pubkey::new_unique(),
+            session_key: key,
+            expires_at: 1_000,
+            bump: 255,
+        };
+
+        assert!(session.is_valid(&game, &key, 999).is_ok());
+        // Expired
+        assert!(session.is_valid(&game, &key, 1_000).is_err());
+        // Wrong game or wrong signer
+        assert!(session.is_valid(&Pubkey::new_unique(), &key, 0).is_err());
+        assert!(session.is_valid(&game, &Pubkey::new_unique(), 0).is_err());
+    }
+
+    #[test]
+    fn test_lowest_hand_scoring() {
+        let players = [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+        let mut game = game_with_players(&players);
+
+        let racks = [
+            vec![number_tile(TileColor::Red, 10), joker_tile()],
+            vec![
+                number_tile(TileColor::Blue, 3),
+                number_tile(TileColor::Black, 4),
+            ],
+            vec![number_tile(TileColor::Orange, 13)],
+        ];
+        for (i, rack) in racks.iter().enumerate() {
+            for tile in rack {
+                game.players[i].add_tile(*tile);
+            }
+        }
+
+        assert_eq!(game.hand_value(0), 40);
+        assert_eq!(game.hand_value(1), 7);
+        assert_eq!(game.lowest_hand(), 1);
+        // A stalled tournament table is decided without the player on turn
+        game.current_turn = 1;
+        assert_eq!(game.lowest_hand_after_forfeit(), 2);
+
+        game.end_game(game.lowest_hand()).unwrap();
+        assert_eq!(game.winner, Some(players[1]));
+        assert_eq!(game.players[1].score, 53);
+        assert_eq!(game.players[0].score, -40);
+    }
+
+    #[test]
+    fn test_profile_record() {
+        let mut profile = PlayerProfile {
+            owner: Pubkey::new_unique(),
+            display_name: String::new(),
+            games_played: 0,
+            games_won: 0,
+            tiles_played: 0,
+            best_winning_score: 0,
+            lifetime_winnings: 0,
+            rating: 1200,
+            rated_games: 0,
+            season_id: 0,
+            season_points: 0,
+            bump: 255,
+        };
+        let mut game = game_with_players(&[profile.owner, Pubkey::new_unique()]);
+
+        game.players[0].tiles_played = 14;
+        game.players[0].score = 42;
+        profile.record(&game.players[0], true, 950, false);
+
+        game.players[0].tiles_played = 5;
+        game.players[0].score = -20;
+        profile.record(&game.players[0], false, 0, false);
+
+        assert_eq!(profile.games_played, 2);
+        assert_eq!(profile.games_won, 1);
+        assert_eq!(profile.tiles_played, 19);
+        assert_eq!(profile.best_winning_score, 42);
+        assert_eq!(profile.lifetime_winnings, 950);
+    }
+
+    #[test]
+    fn test_rating_deltas() {
+        let players = [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+        let mut game = game_with_players(&players);
+        game.rated = true;
+        for (i, rating) in [1200u16, 1200, 1600].iter().enumerate() {
+            game.players[i].rating = *rating;
+        }
+        game.players[0].score = 30;
+        game.players[1].score = -10;
+        game.players[2].score = -20;
+
+        game.compute_rating_deltas();
+
+        // The favourite finishing last loses the most
+        assert_eq!(game.players[0].rating_delta, 22);
+        assert_eq!(game.players[1].rating_delta, 6);
+        assert_eq!(game.players[2].rating_delta, -29);
+
+        // Equal ratings and scores leave everyone unchanged
+        let mut even = game_with_players(&players[..2]);
+        even.compute_rating_deltas();
+        assert_eq!(even.players[0].rating_delta, 0);
+        assert_eq!(even.players[1].rating_delta, 0);
+    }
+
+    #[test]
+    fn test_season_leaderboard_sorted_and_bounded() {
+        let mut season = Season {
+            season_id: 1,
+            start_ts: 100,
+            end_ts: 200,
+            reward_pool: 0,
+            finalized: false,
+            bump: 255,
+            payout_bps: vec![],
+            leaderboard: vec![],
+        };
+
+        let players: Vec<Pubkey> = (0..12).map(|_| Pubkey::new_unique()).collect();
+        for (i, player) in players.iter().enumerate() {
+            season.submit(*player, i as u32);
+        }
+        assert_eq!(season.leaderboard.len(), 10);
+        assert_eq!(season.leaderboard[0].player, players[11]);
+        assert_eq!(season.leaderboard[9].points, 2);
+
+        // An existing entry moves up instead of being duplicated
+        season.submit(players[5], 50);
+        assert_eq!(season.leaderboard.len(), 10);
+        assert_eq!(season.leaderboard[0].player, players[5]);
+        assert_eq!(
+            season
+                .leaderboard
+                .iter()
+                .filter(|e| e.player == players[5])
+                .count(),
+            1
+        );
+
+        assert!(season.contains(100));
+        assert!(!season.contains(200));
+    }
+
+    fn tournament(format: TournamentFormat, table_size: u8, entrants: usize) -> Tournament {
+        Tournament {
+            tournament_id: 1,
+            authority: Pubkey::new_unique(),
+            format,
+            table_size,
+            buy_in: 0,
+            max_entrants: entrants as u8,
+            status: TournamentStatus::Running,
+            current_round: 0,
+            prize_pool: 0,
+            bump: 255,
+            created_at: 0,
+            payout_bps: vec![],
+            entrants: (0..entrants)
+                .map(|_| Entrant {
+                    player: Pubkey::new_unique(),
+                    points: 0,
+                    eliminated_round: 0,
+                })
+                .collect(),
+            seating: vec![],
+            tables: vec![],
+        }
+    }
+
+    #[test]
+    fn test_single_elimination_bracket() {
+        let mut t = tournament(TournamentFormat::SingleElimination, 2, 5);
+
+        // 5 players at tables of 2: sizes 2, 2 and a bye
+        t.seat_round().unwrap();
+        let sizes: Vec<u8> = t.tables.iter().map(|t| t.size).collect();
+        assert_eq!(sizes, vec![2, 2, 1]);
+        assert!(t.tables.iter().all(|t| t.game_id == 0));
+        assert!(!t.round_complete()); // Only the bye is decided
+
+        t.tables[0].game_id = 100; // Created by create_table_game
+        assert!(!t.table_reported(100));
+        t.apply_result(0, 1); // Entrant 1 beats 0
+        assert!(t.table_reported(100));
+        t.apply_result(1, 2); // Entrant 2 beats 3
+        assert!(t.round_complete());
+        assert_eq!(t.entrants[0].eliminated_round, 1);
+        assert_eq!(t.entrants[4].eliminated_round, 0);
+
+        // Round 2: entrants 1, 2, 4 -> a table of 2 and a bye
+        t.seat_round().unwrap();
+        assert_eq!(t.seating, vec![1, 2, 4]);
+        t.apply_result(0, 1); // Entrant 2 beats 1
+        assert!(t.status == TournamentStatus::Running);
+
+        // Final: 2 vs 4
+        t.seat_round().unwrap();
+        t.apply_result(0, 1); // Entrant 4 wins
+        assert!(t.status == TournamentStatus::Finished);
+
+        let standings = t.standings();
+        assert_eq!(&standings[..3], &[4, 2, 1]);
+    }
+
+    #[test]
+    fn test_swiss_rounds() {
+        let mut t = tournament(TournamentFormat::Swiss { rounds: 2 }, 3, 6);
+
+        t.seat_round().unwrap();
+        assert_eq!(t.tables.len(), 2);
+        t.apply_result(0, 2); // Entrant 2 wins table 0
+        t.apply_result(1, 3); // Entrant 3 wins table 1
+        assert!(t.status == TournamentStatus::Running);
+
+        // Winners are seated together in round 2
+        t.seat_round().unwrap();
+        assert_eq!(&t.seating[..2], &[2, 3]);
+        t.apply_result(0, 1); // Entrant 3 wins again
+        t.apply_result(1, 3);
+        assert!(t.status == TournamentStatus::Finished);
+        assert_eq!(t.standings()[0], 3);
+        assert_eq!(t.entrants[3].points, 2);
+    }
+
+    #[test]
+    fn test_match_queue_rating_band() {
+        let entry = |rating| QueueEntry {
+            player: Pubkey::new_unique(),
+            rating,
+            has_profile: true,
+        };
+        let mut queue = MatchQueue {
+            stake: 0,
+            player_count: 2,
+            rating_band: 0,
+            bump: 0,
+            waiting: vec![entry(1500), entry(1100), entry(1200), entry(1520)],
+        };
+
+        // Without a band the two oldest players are matched
+        assert_eq!(queue.pick_group(), Some(vec![0, 1]));
+
+        // With a band the oldest player waits for someone close to them
+        queue.rating_band = 50;
+        assert_eq!(queue.pick_group(), Some(vec![0, 3]));
+
+        queue.waiting.remove(3);
+        assert_eq!(queue.pick_group(), None);
+
+        queue.rating_band = 100;
+        assert_eq!(queue.pick_group(), Some(vec![1, 2]));
+
+        // The band bounds the whole table, not each player's gap to the oldest
+        queue.player_count = 3;
+        queue.rating_band = 50;
+        queue.waiting = vec![entry(1500), entry(1450), entry(1550), entry(1480)];
+        assert_eq!(queue.pick_group(), Some(vec![0, 1, 3]));
+    }
+
+    #[test]
+    fn test_lobby_page_listing() {
+        let entry = |game_id| LobbyEntry {
+            game_id,
+            stake: 100,
+            seats_left: 2,
+            options: GameOptions::default(),
+            private: false,
+            created_at: 0,
+        };
+        let mut lobby = LobbyPage {
+            page: 0,
+            bump: 0,
+            entries: Vec::new(),
+        };
+        for id in 0..crate::constants::LOBBY_PAGE_SIZE as u64 {
+            lobby.list(entry(id)).unwrap();
+        }
+        assert!(lobby.list(entry(99)).is_err());
+
+        lobby.update(3, 1);
+        assert_eq!(lobby.entries[3].seats_left, 1);
+
+        // A full table drops off the page
+        lobby.update(3, 0);
+        assert!(!lobby.entries.iter().any(|e| e.game_id == 3));
+        lobby.list(entry(99)).unwrap();
+    }
+
+    #[test]
+    fn test_private_game_access() {
+        let host = Pubkey::new_unique();
+        let friend = Pubkey::new_unique();
+        let stranger = Pubkey::new_unique();
+        let mut game = game_with_players(&[host]);
+        game.max_players = 3;
+        game.access = GameAccess {
+            allowlist: vec![host, stranger],
+            password_hash: Some(password_hash(game.game_id, b"tiles")),
+            reserved: vec![friend],
+        };
+
+        // Reserved players skip the allowlist and password
+        assert!(game.check_access(&friend, None).is_ok());
+        assert!(game.check_access(&stranger, None).is_err());
+        assert!(game.check_access(&stranger, Some(b"rummy")).is_err());
+        assert!(game.check_access(&stranger, Some(b"tiles")).is_ok());
+        assert!(game
+            .check_access(&Pubkey::new_unique(), Some(b"tiles"))
+            .is_err());
+
+        // The last free seat is held for the friend
+        game.players[1].pubkey = stranger;
+        game.current_players = 2;
+        assert!(game.check_access(&host, Some(b"tiles")).is_err());
+        assert!(game.check_access(&friend, None).is_ok());
+    }
+
+    #[test]
+    fn test_remove_player_shifts_seats() {
+        let players = [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+        let mut game = game_with_players(&players);
+        game.max_players = 4;
+        game.entry_fee = 100;
+        game.prize_pool = 300;
+        game.players[0].add_tile(joker_tile());
+
+        assert_eq!(game.remove_player(0).unwrap(), 100);
+        assert_eq!(game.current_players, 2);
+        assert_eq!(game.prize_pool, 200);
+        assert!(game.players[0].pubkey == players[1]);
+        assert!(game.players[1].pubkey == players[2]);
+        assert!(game.players[2].pubkey == Pubkey::default());
+        assert_eq!(game.tiles_remaining, 1);
+        assert_eq!(game.tile_pool.len(), 1);
+    }
+
+    #[test]
+    fn test_rematch_needs_claimed_game() {
+        let mut game = game_with_players(&[Pubkey::new_unique(), Pubkey::new_unique()]);
+        game.game_status = GameStatus::InProgress;
+        assert!(game.reset_for_rematch(0).is_err());
+
+        // Finished but the prize is still unclaimed
+        game.game_status = GameStatus::Finished;
+        game.prize_pool = 100;
+        assert!(game.reset_for_rematch(0).is_err());
+    }
+
+    #[test]
+    fn test_match_pays_highest_total() {
+        let players = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let mut game = game_with_players(&players);
+        game.match_target = Some(MatchTarget::Score { points: 50 });
+        game.rounds_played = 2;
+        game.players[0].match_score = 45;
+        game.players[1].match_score = 45;
+        game.players[0].add_tile(number_tile(TileColor::Red, 10));
+        assert!(!game.match_over());
+
+        // Player 1 goes out and passes the target
+        assert_eq!(game.end_round(1).unwrap(), Some(1));
+        assert_eq!(game.rounds_played, 3);
+        assert_eq!(game.players[1].match_score, 55);
+        assert_eq!(game.players[0].match_score, 35);
+        assert_eq!(game.winner, Some(players[1]));
+        // Totals are reported as the final scores
+        assert_eq!(game.players[1].score, 55);
+    }
+
+    #[test]
+    fn test_match_round_waits_for_vrf_deal() {
+        let players = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let mut game = game_with_players(&players);
+        game.game_status = GameStatus::InProgress;
+        game.match_target = Some(MatchTarget::Rounds { rounds: 3 });
+        game.players[0].add_tile(number_tile(TileColor::Red, 10));
+
+        // The round ends with empty racks and an unshuffled pool
+        assert_eq!(game.end_round(1).unwrap(), None);
+        assert!(game.awaiting_deal());
+        assert_eq!(game.players[0].tile_count, 0);
+        assert_eq!(game.current_turn, 1);
+        assert!(game.verify_turn(&players[1]).is_err());
+
+        game.deal_round_with_randomness([7; 32]).unwrap();
+        assert!(!game.awaiting_deal());
+        let hand_size = game.rules.hand_size;
+        assert_eq!(game.players[0].tile_count, hand_size);
+        assert_eq!(game.players[1].tile_count, hand_size);
+        assert_eq!(game.tiles_remaining as usize, game.tile_pool.len(),);
+        assert!(game.verify_turn(&players[1]).is_ok());
+
+        // Dealing again is refused
+        assert!(game.deal_round_with_randomness([7; 32]).is_err());
+    }
+
+    #[test]
+    fn test_match_tie_goes_to_round_winner() {
+        let players = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let mut game = game_with_players(&players);
+        game.match_target = Some(MatchTarget::Rounds { rounds: 1 });
+        game.players[0].add_tile(number_tile(TileColor::Red, 5));
+        game.players[0].match_score = 10;
+
+        // Player 1 goes out for 5 points while player 0 drops to 5 as well
+        assert_eq!(game.end_round(1).unwrap(), Some(1));
+        assert_eq!(game.players[0].match_score, 5);
+        assert_eq!(game.players[1].match_score, 5);
+        assert_eq!(game.winner, Some(players[1]));
+    }
+
+    #[test]
+    fn test_team_scoring() {
+        let players: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let mut game = game_with_players(&players);
+        game.teams = true;
+        game.rated = true;
+        for (i, (rack, rating)) in [
+            (vec![number_tile(TileColor::Red, 5)], 1300),
+            (vec![number_tile(TileColor::Blue, 10)], 1250),
+            (vec![], 1100),
+            (vec![number_tile(TileColor::Black, 3)], 1150),
+        ]
+        .iter()
+        .enumerate()
+        {
+            for tile in rack {
+                game.players[i].add_tile(*tile);
+            }
+            game.players[i].rating = *rating;
+        }
+
+        // Seat 2 goes out; seat 0 wins with them
+        game.prize_pool = 1001;
+        game.end_game(2).unwrap();
+        assert_eq!(game.players[2].score, 18);
+        assert!(game.is_winner(0) && game.is_winner(2));
+        assert!(!game.is_winner(1) && !game.is_winner(3));
+
+        // 951 after the 5% fee, split between the partners
+        assert_eq!(game.payout, 951);
+        assert_eq!(game.winnings(2), 476);
+        assert_eq!(game.winnings(0), 475);
+        assert_eq!(game.winnings(1), 0);
+
+        // Partners are rated as a team (1200 each here) and move together
+        assert_eq!(game.players[0].rating_delta, 16);
+        assert_eq!(game.players[2].rating_delta, 16);
+        assert_eq!(game.players[1].rating_delta, -16);
+        assert_eq!(game.players[3].rating_delta, -16);
+    }
+
+    #[test]
+    fn test_partner_melds_only_extend_table() {
+        let players: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let mut game = game_with_players(&players);
+        game.teams = true;
+        game.partner_melds = true;
+        game.players[2].has_opened = true;
+        let run: Vec<Tile> = (4..=6).map(|n| number_tile(TileColor::Red, n)).collect();
+        game.table_melds = vec![Meld {
+            meld_type: MeldType::Run,
+            tiles: run.clone(),
+        }];
+        for color in [TileColor::Red, TileColor::Blue, TileColor::Black] {
+            game.players[0].add_tile(number_tile(color, 1));
+        }
+        game.players[0].add_tile(number_tile(TileColor::Red, 7));
+
+        // A new set of 1s is an initial meld of 3 points, partner or not
+        let new_set = Meld {
+            meld_type: MeldType::Set,
+            tiles: game.players[0].tiles[..3].to_vec(),
+        };
+        let result = game.clone().execute_play(
+            0,
+            (0..3).map(|i| TilePlay { tile_index: i }).collect(),
+            vec![game.table_melds[0].clone(), new_set],
+        );
+        assert!(result.is_err());
+
+        // Adding the red 7 to the run on the table is allowed
+        let mut extended = run;
+        extended.push(number_tile(TileColor::Red, 7));
+        game.execute_play(
+            0,
+            vec![TilePlay { tile_index: 3 }],
+            vec![Meld {
+                meld_type: MeldType::Run,
+                tiles: extended,
+            }],
+        )
+        .unwrap();
+        assert_eq!(game.players[0].tile_count, 3);
+        assert_eq!(game.table_melds[0].tiles.len(), 4);
+        // The player still has to make their own initial meld
+        assert!(!game.players[0].has_opened);
+    }
+
+    #[test]
+    fn test_six_player_edition() {
+        let players: Vec<Pubkey> = (0..6).map(|_| Pubkey::new_unique()).collect();
+        let mut game = game_with_players(&players);
+        game.rules.jokers = 4;
+        assert!(game.is_six_player());
+
+        // 1-13 in four colors, three copies each, plus the jokers
+        game.build_tile_pool();
+        assert_eq!(game.tile_pool.len(), 160);
+        assert_eq!(game.tiles_remaining, 160);
+        let red_sevens = game
+            .tile_pool
+            .iter()
+            .filter(|t| **t == number_tile(TileColor::Red, 7))
+            .count();
+        assert_eq!(red_sevens, 3);
+
+        // Seats 5 and 6 take their turns and can be found
+        assert_eq!(game.get_player_index(&players[5]).unwrap(), 5);
+        for _ in 0..5 {
+            game.next_turn();
+        }
+        assert_eq!(game.current_turn, 5);
+        game.next_turn();
+        assert_eq!(game.current_turn, 0);
+
+        // Every other seat's rack counts towards the winner's score
+        game.seat_mut(5).add_tile(number_tile(TileColor::Blue, 9));
+        game.seat_mut(1).add_tile(joker_tile());
+        game.end_game(3).unwrap();
+        assert_eq!(game.seat(5).score, -9);
+        assert_eq!(game.seat(3).score, 39);
+        assert!(game.winner == Some(players[3]));
+
+        // Queues and tournaments seat five or six with the edition's jokers
+        assert!(GameOptions::for_players(4) == GameOptions::default());
+        assert_eq!(GameOptions::for_players(5).rules.jokers, SIX_PLAYER_JOKERS);
+    }
+
+    #[test]
+    fn test_game_space_by_edition() {
+        assert_eq!(GameState::space(4), GameState::SPACE);
+
+        // A full pool and a fully reserved table must fit either layout
+        for seats in [4, 6] {
+            let players: Vec<Pubkey> = (0..seats).map(|_| Pubkey::new_unique()).collect();
+            let mut game = game_with_players(&players);
+            game.rules.jokers = MAX_JOKERS as u8;
+            game.access.reserved = players.clone();
+            game.build_tile_pool();
+
+            let mut data = Vec::new();
+            game.serialize(&mut data).unwrap();
+            assert!(8 + data.len() <= GameState::space(seats as u8));
+        }
+        assert!(GameState::space(6) > GameState::SPACE);
+    }
+
+    #[test]
+    fn test_rack_grows_past_21_tiles() {
+        let players: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let mut game = game_with_players(&players);
+        game.rules.jokers = MAX_JOKERS as u8;
+        game.build_tile_pool();
+
+        // Worst case: one rack holds nearly the whole set, the rest is on the table
+        let table: Vec<Tile> = game.tile_pool.drain(..MAX_TABLE_MELDS * 3).collect();
+        for tile in std::mem::take(&mut game.tile_pool) {
+            game.players[0].add_tile(tile);
+        }
+        game.tiles_remaining = 0;
+        assert_eq!(game.players[0].tile_count as usize, TOTAL_TILES - 90);
+        game.table_melds = table
+            .chunks(3)
+            .map(|tiles| Meld {
+                meld_type: MeldType::Run,
+                tiles: tiles.to_vec(),
+            })
+            .collect();
+
+        let mut data = Vec::new();
+        game.serialize(&mut data).unwrap();
+        assert!(8 + data.len() <= GameState::SPACE);
+
+        game.players[0].remove_tile(0).unwrap();
+        assert_eq!(
+            game.players[0].tiles.len(),
+            game.players[0].tile_count as usize
+        );
+    }
+
+    #[test]
+    fn test_table_and_meld_limits() {
+        let players = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let mut game = game_with_players(&players);
+        game.players[0].has_opened = true;
+        for n in 1..=3 {
+            game.players[0].add_tile(number_tile(TileColor::Red, n));
+        }
+
+        let set = |n| Meld {
+            meld_type: MeldType::Set,
+            tiles: vec![
+                number_tile(TileColor::Blue, n),
+                number_tile(TileColor::Black, n),
+                number_tile(TileColor::Orange, n),
+            ],
+        };
+        game.table_melds = (0..MAX_TABLE_MELDS)
+            .map(|i| set(i as u8 % 13 + 1))
+            .collect();
+
+        // A 31st meld is over the cap even though every meld is valid
+        let mut melds = game.table_melds.clone();
+        melds.push(Meld {
+            meld_type: MeldType::Run,
+            tiles: game.players[0].tiles.clone(),
+        });
+        let plays = || (0..3).map(|i| TilePlay { tile_index: i }).collect();
+        assert!(game
+            .clone()
+            .execute_play(0, plays(), melds.clone())
+            .is_err());
+
+        // The same play fits with one meld less on the table
+        game.table_melds.pop();
+        melds.remove(0);
+        game.execute_play(0, plays(), melds).unwrap();
+
+        // No meld may hold more than a full 1-13 run
+        let meld = Meld {
+            meld_type: MeldType::Run,
+            tiles: vec![joker_tile(); MAX_MELD_TILES + 1],
+        };
+        assert!(meld.validate(&GameRules::default()).is_err());
+    }
+
+    #[test]
+    fn test_compact_tile_encoding() {
+        let colors = [
+            TileColor::Red,
+            TileColor::Blue,
+            TileColor::Black,
+            TileColor::Orange,
+        ];
+        let mut codes = Vec::new();
+        for color in colors {
+            for number in 1..=13 {
+                let tile = Tile::number(color, number);
+                assert!(tile.tile_type() == TileType::Number { color, number });
+                codes.push(tile.code);
+            }
+        }
+        assert!(Tile::JOKER.tile_type() == TileType::Joker);
+        assert!(Tile::default().tile_type() == TileType::Empty);
+        codes.push(Tile::JOKER.code);
+        codes.push(Tile::default().code);
+        codes.sort();
+        codes.dedup();
+        assert_eq!(codes.len(), 54);
+
+        // Codes outside the encoding never decode as a playable tile
+        for code in [0x0e, 0x40, 0x81, 0xff] {
+            assert!(Tile { code }.tile_type() == TileType::Empty);
+        }
+
+        let mut data = Vec::new();
+        Tile::JOKER.serialize(&mut data).unwrap();
+        assert_eq!(data.len(), Tile::SPACE);
+    }
+
+    #[test]
+    fn test_game_discriminators() {
+        let hash = |name: &str| solana_sha256_hasher::hash(name.as_bytes()).to_bytes();
+        assert_eq!(
+            GAME_DISCRIMINATOR[..8],
+            hash("account:VersionedGameState")[..8]
+        );
+        assert_eq!(GAME_DISCRIMINATOR[8], GAME_VERSION);
+        assert_eq!(LEGACY_GAME_DISCRIMINATOR, &hash("account:GameState")[..8]);
+        assert_eq!(GameState::DISCRIMINATOR, GAME_DISCRIMINATOR);
+    }
+
+    // A finished, unclaimed four-player game in the original (v0) layout
+    fn sample_game_v0() -> GameStateV0 {
+        let key = |n: u8| Pubkey::new_from_array([n; 32]);
+        let tile = |tile: Tile| TileV0 {
+            tile_type: tile.tile_type(),
+        };
+        let player = |n: u8, tiles: &[Tile]| {
+            let mut rack = [TileV0::default(); RACK_SLOTS_V0];
+            for (slot, t) in rack.iter_mut().zip(tiles) {
+                *slot = tile(*t);
+            }
+            PlayerV0 {
+                pubkey: key(n),
+                tiles: rack,
+                tile_count: tiles.len() as u8,
+                has_opened: n != 3,
+                score: -(n as i16),
+            }
+        };
+
+        GameStateV0 {
+            game_id: 0x0102_0304_0506_0708,
+            authority: key(0xaa),
+            max_players: 4,
+            current_players: 4,
+            current_turn: 2,
+            game_status: GameStatus::Finished,
+            winner: Some(key(2)),
+            prize_pool: 400_000_000,
+            bump: 254,
+            players: [
+                player(1, &[number_tile(TileColor::Red, 1), joker_tile()]),
+                player(2, &[]),
+                player(3, &[number_tile(TileColor::Blue, 13); 21]),
+                player(4, &[number_tile(TileColor::Orange, 7); 3]),
+            ],
+            table_melds: vec![MeldV0 {
+                meld_type: MeldType::Set,
+                tiles: [
+                    number_tile(TileColor::Red, 9),
+                    number_tile(TileColor::Black, 9),
+                    joker_tile(),
+                ]
+                .map(tile)
+                .to_vec(),
+            }],
+            tile_pool: [number_tile(TileColor::Black, 2), joker_tile()]
+                .map(tile)
+                .to_vec(),
+            tiles_remaining: 2,
+        }
+    }
+
+    // A six-player game mid-round in the current layout, touching every field
+    fn sample_game() -> GameState {
+        let key = |n: u8| Pubkey::new_from_array([n; 32]);
+        let player = |n: u8, tiles: &[Tile]| Player {
+            pubkey: key(n),
+            tiles: tiles.to_vec(),
+            tile_count: tiles.len() as u8,
+            has_opened: n.is_multiple_of(2),
+            score: -(n as i16),
+            tiles_played: n as u16,
+            has_profile: true,
+            result_recorded: false,
+            rating: 1200 + n as u16,
+            rating_delta: n as i16,
+            match_score: 10 * n as i16,
+        };
+
+        GameState {
+            game_id: 0x0102_0304_0506_0708,
+            authority: key(0xaa),
+            max_players: 6,
+            current_players: 6,
+            current_turn: 4,
+            game_status: GameStatus::InProgress,
+            winner: None,
+            prize_pool: 600_000_000,
+            bump: 254,
+            players: [
+                player(1, &[number_tile(TileColor::Red, 1), joker_tile()]),
+                player(2, &[number_tile(TileColor::Blue, 13)]),
+                player(3, &[]),
+                player(4, &[number_tile(TileColor::Orange, 7); 3]),
+            ],
+            table_melds: vec![Meld {
+                meld_type: MeldType::Set,
+                tiles: vec![
+                    number_tile(TileColor::Red, 9),
+                    number_tile(TileColor::Black, 9),
+                    joker_tile(),
+                ],
+            }],
+            tile_pool: vec![number_tile(TileColor::Black, 2), joker_tile()],
+            tiles_remaining: 2,
+            bankroll_funded: true,
+            commit_policy: CommitPolicy::EveryNTurns { turns: 5 },
+            commit_frequency_ms: 30_000,
+            turn_number: 41,
+            last_committed_turn: 40,
+            last_activity: 1_700_000_000,
+            rated: true,
+            finished_at: 0,
+            tournament: Some(key(0xbb)),
+            entry_fee: 100_000_000,
+            lobby_page: Some(3),
+            access: GameAccess {
+                allowlist: vec![key(1), key(2)],
+                password_hash: Some([0xcc; 32]),
+                reserved: vec![key(5)],
+            },
+            match_target: Some(MatchTarget::Score { points: 200 }),
+            rounds_played: 1,
+            teams: false,
+            partner_melds: false,
+            rules: GameRules {
+                jokers: 4,
+                ..GameRules::default()
+            },
+            extra_players: vec![
+                player(5, &[number_tile(TileColor::Blue, 3)]),
+                player(6, &[]),
+            ],
+            payout: 0,
+        }
+    }
+
+    fn account_data(discriminator: &[u8], game: &impl AnchorSerialize) -> Vec<u8> {
+        let mut data = discriminator.to_vec();
+        game.serialize(&mut data).unwrap();
+        data
+    }
+
+    // Compare `data` with fixtures/<name>.hex. Run with UPDATE_GOLDEN=1 to
+    // rewrite the file after a deliberate layout change.
+    fn assert_golden(name: &str, data: &[u8]) {
+        let path = format!("{}/fixtures/{}.hex", env!("CARGO_MANIFEST_DIR"), name);
+        let hex: Vec<String> = data
+            .chunks(32)
+            .map(|line| line.iter().map(|b| format!("{:02x}", b)).collect())
+            .collect();
+        let hex = hex.join("\n") + "\n";
+        if std::env::var("UPDATE_GOLDEN").is_ok() {
+            std::fs::write(&path, &hex).unwrap();
+        }
+        let golden = std::fs::read_to_string(&path).unwrap();
+        assert!(golden == hex, "{} layout changed", name);
+    }
+
+    #[test]
+    fn test_golden_layouts() {
+        let v0 = account_data(LEGACY_GAME_DISCRIMINATOR, &sample_game_v0());
+        let v1 = account_data(GAME_DISCRIMINATOR, &sample_game());
+
+        assert_golden("game_v0", &v0);
+        assert_golden("game_v1", &v1);
+
+        // Every version loads as the current layout
+        let upgraded = GameState::from(sample_game_v0());
+        for (version, data, expected) in [(0, &v0, &upgraded), (GAME_VERSION, &v1, &sample_game())]
+        {
+            assert_eq!(game_version(data), Some(version));
+            let game = load_game(data).unwrap();
+            assert_eq!(
+                account_data(GAME_DISCRIMINATOR, &game),
+                account_data(GAME_DISCRIMINATOR, expected)
+            );
+        }
+    }
+
+    #[test]
+    fn test_migrate_finished_unclaimed_game() {
+        let old = sample_game_v0();
+        let game = load_game(&account_data(LEGACY_GAME_DISCRIMINATOR, &old)).unwrap();
+
+        // Still claimable by the same winner for the same pool
+        assert!(game.game_status == GameStatus::Finished);
+        assert_eq!(game.winner, old.winner);
+        assert_eq!(game.prize_pool, old.prize_pool);
+        assert!(!game.bankroll_funded);
+
+        // Racks keep their tiles and drop the empty slots
+        for (player, before) in game.players.iter().zip(&old.players) {
+            assert_eq!(player.pubkey, before.pubkey);
+            assert_eq!(player.tile_count, before.tile_count);
+            assert_eq!(player.tiles.len(), before.tile_count as usize);
+            for (tile, slot) in player.tiles.iter().zip(&before.tiles) {
+                assert!(*tile == Tile::from(*slot));
+            }
+            assert_eq!(player.score, before.score);
+            assert_eq!(player.has_opened, before.has_opened);
+        }
+        assert_eq!(game.players[2].tiles.len(), RACK_SLOTS_V0);
+    }
+
+    #[test]
+    fn test_load_baseline_game_bytes() {
+        // A finished two-player game as the first deployed program wrote it:
+        // Borsh enum tiles (number 3 bytes, joker and empty 1) in fixed
+        // 21-slot racks
+        let rack = |tiles: &[&[u8]]| {
+            let mut bytes: Vec<u8> = tiles.concat();
+            bytes.extend([2; RACK_SLOTS_V0].iter().skip(tiles.len()));
+            bytes
+        };
+        let mut data = LEGACY_GAME_DISCRIMINATOR.to_vec();
+        data.extend(7u64.to_le_bytes()); // game_id
+        data.extend([0xaa; 32]); // authority
+        data.extend([2, 2, 0, 2]); // max/current players, turn, Finished
+        data.push(1); // winner: Some
+        data.extend([1; 32]);
+        data.extend(200_000_000u64.to_le_bytes()); // prize_pool
+        data.push(255); // bump
+        for (key, tiles, count, opened, score) in [
+            (1u8, rack(&[]), 0u8, 1u8, 12i16),
+            (2, rack(&[&[0, 0, 5], &[1]]), 2, 0, -35),
+            (0, rack(&[]), 0, 0, 0),
+            (0, rack(&[]), 0, 0, 0),
+        ] {
+            data.extend([key; 32]);
+            data.extend(tiles);
+            data.extend([count, opened]);
+            data.extend(score.to_le_bytes());
+        }
+        data.extend(1u32.to_le_bytes()); // one run of blue 3-5
+        data.push(1);
+        data.extend(3u32.to_le_bytes());
+        data.extend([0, 1, 3, 0, 1, 4, 0, 1, 5]);
+        data.extend(1u32.to_le_bytes()); // pool: orange 13
+        data.extend([0, 3, 13]);
+        data.extend(1u16.to_le_bytes()); // tiles_remaining
+
+        assert_eq!(game_version(&data), Some(0));
+        let game = load_game(&data).unwrap();
+        assert_eq!(game.game_id, 7);
+        assert!(game.game_status == GameStatus::Finished);
+        assert_eq!(game.winner, Some(Pubkey::new_from_array([1; 32])));
+        assert_eq!(game.prize_pool, 200_000_000);
+        assert!(game.players[0].has_opened);
+        assert_eq!(game.players[0].score, 12);
+        assert!(game.players[0].tiles.is_empty());
+        assert!(game.players[1].tiles == [number_tile(TileColor::Red, 5), joker_tile()]);
+        assert_eq!(game.players[1].tile_count, 2);
+        assert_eq!(game.players[1].score, -35);
+        assert!(game.table_melds[0].meld_type == MeldType::Run);
+        assert!(game.table_melds[0].tiles == [3, 4, 5].map(|n| number_tile(TileColor::Blue, n)));
+        assert!(game.tile_pool == [number_tile(TileColor::Orange, 13)]);
+        assert_eq!(game.tiles_remaining, 1);
+
+        // Fields added since take their defaults
+        assert_eq!(game.entry_fee, ENTRY_FEE_LAMPORTS);
+        assert_eq!(game.rules.jokers, 2);
+        assert!(game.match_target.is_none());
+        assert!(game.extra_players.is_empty());
+        assert!(!game.players[1].has_profile);
+    }
+
+    #[test]
+    fn test_migration_sizes_and_versions() {
+        let v0 = account_data(LEGACY_GAME_DISCRIMINATOR, &sample_game_v0());
+        let v1 = account_data(GAME_DISCRIMINATOR, &sample_game());
+
+        // A full 21-tile rack migrates within the space of a new game
+        let migrated = account_data(GAME_DISCRIMINATOR, &load_game(&v0).unwrap());
+        assert!(migrated.len() <= 8 + GameState::space(4));
+        assert!(v1.len() <= 8 + GameState::space(6));
+
+        // A version this build does not know, or another account, is refused
+        let mut future = v1.clone();
+        future[8] = GAME_VERSION + 1;
+        assert!(load_game(&future).is_err());
+        assert!(load_game(&[0; 16]).is_err());
+        assert_eq!(game_version(&[0; 16]), None);
     }
 }
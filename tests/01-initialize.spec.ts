@@ -12,6 +12,8 @@ import {
   createGamePDAs,
   setupEphemeralValidator,
   airdropToPlayers,
+  ensureConfig,
+  DEFAULT_GAME_OPTIONS,
 } from "./helpers";
 
 describe("01 - Initialize Game", () => {
@@ -30,6 +32,7 @@ describe("01 - Initialize Game", () => {
     console.log("Treasury PDA:", treasuryPDA.toString());
 
     ephemeralValidator = await setupEphemeralValidator(connection, isLocalnet);
+    await ensureConfig(program, connection, providerMagic);
     console.log("Validator:", ephemeralValidator.identity);
 
     const balance = await connection.getBalance(
@@ -46,7 +49,7 @@ describe("01 - Initialize Game", () => {
     const start = Date.now();
 
     const tx = await program.methods
-      .initializeGame(gameId, 3)
+      .initializeGame(gameId, 3, DEFAULT_GAME_OPTIONS, null)
       .accounts({
         game: gamePDA,
         authority: providerMagic.wallet.publicKey,
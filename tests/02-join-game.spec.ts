@@ -12,6 +12,8 @@ import {
   createGamePDAs,
   setupEphemeralValidator,
   airdropToPlayers,
+  ensureConfig,
+  DEFAULT_GAME_OPTIONS,
 } from "./helpers";
 
 describe("02 - Join Game", () => {
@@ -19,7 +21,7 @@ describe("02 - Join Game", () => {
   const program: Program<Rummikub> = anchor.workspace.Rummikub;
   const { player1, player2, player3 } = createTestPlayers();
   const gameId = new anchor.BN(Date.now());
-  const { gamePDA, treasuryPDA } = createGamePDAs(program, gameId);
+  const { gamePDA, escrowPDA, treasuryPDA } = createGamePDAs(program, gameId);
 
   let ephemeralValidator: any;
 
@@ -30,6 +32,7 @@ describe("02 - Join Game", () => {
     console.log("Treasury PDA:", treasuryPDA.toString());
 
     ephemeralValidator = await setupEphemeralValidator(connection, isLocalnet);
+    await ensureConfig(program, connection, providerMagic);
     console.log("Validator:", ephemeralValidator.identity);
 
     await airdropToPlayers(connection, isLocalnet, [player1, player2, player3]);
@@ -37,7 +40,7 @@ describe("02 - Join Game", () => {
     // Initialize game first
     console.log("Initializing game...");
     const tx = await program.methods
-      .initializeGame(gameId, 3)
+      .initializeGame(gameId, 3, DEFAULT_GAME_OPTIONS, null)
       .accounts({
         game: gamePDA,
         authority: providerMagic.wallet.publicKey,
@@ -90,6 +93,15 @@ describe("02 - Join Game", () => {
     console.log(
       `  Current Players: ${gameState.currentPlayers}/${gameState.maxPlayers}`
     );
+
+    // Entry fee is held by the escrow PDA, not the game account
+    const escrowRent = await connection.getMinimumBalanceForRentExemption(
+      8 + 9
+    );
+    const escrowBalance = await connection.getBalance(escrowPDA);
+    console.log(
+      `  Escrow: ${(escrowBalance - escrowRent) / LAMPORTS_PER_SOL} SOL`
+    );
   });
 
   it("should allow Player 2 to join with 0.1 SOL entry fee", async () => {
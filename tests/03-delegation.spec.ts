@@ -12,7 +12,10 @@ import {
   createGamePDAs,
   setupEphemeralValidator,
   airdropToPlayers,
+  ensureConfig,
+  DEFAULT_GAME_OPTIONS,
 } from "./helpers";
+import { assert } from "chai";
 
 describe("03 - Delegation to ER", () => {
   const { connection, erConnection, isLocalnet, providerMagic } =
@@ -30,6 +33,7 @@ describe("03 - Delegation to ER", () => {
     console.log("Game PDA:", gamePDA.toString());
 
     ephemeralValidator = await setupEphemeralValidator(connection, isLocalnet);
+    await ensureConfig(program, connection, providerMagic);
     console.log("Validator:", ephemeralValidator.identity);
 
     await airdropToPlayers(connection, isLocalnet, [player1, player2, player3]);
@@ -37,7 +41,7 @@ describe("03 - Delegation to ER", () => {
     // Initialize game
     console.log("Initializing game...");
     let tx = await program.methods
-      .initializeGame(gameId, 3)
+      .initializeGame(gameId, 3, DEFAULT_GAME_OPTIONS, null)
       .accounts({
         game: gamePDA,
         authority: providerMagic.wallet.publicKey,
@@ -94,7 +98,7 @@ describe("03 - Delegation to ER", () => {
     ];
 
     const tx = await program.methods
-      .delegate()
+      .delegate({ everyNTurns: { turns: 2 } }, 0)
       .accounts({
         payer: providerMagic.wallet.publicKey,
         game: gamePDA,
@@ -137,6 +141,10 @@ describe("03 - Delegation to ER", () => {
     console.log(`   Players: ${gameState.currentPlayers}`);
     console.log(`   Prize Pool: ${gameState.prizePool / LAMPORTS_PER_SOL} SOL`);
     console.log(`   Current Turn: Player ${gameState.currentTurn + 1}`);
+    console.log(`   Commit Policy: ${JSON.stringify(gameState.commitPolicy)}`);
+
+    assert.deepEqual(gameState.commitPolicy, { everyNTurns: { turns: 2 } });
+    assert.equal(gameState.lastCommittedTurn, gameState.turnNumber);
   });
 
   it("should verify game state is accessible on ER", async () => {
@@ -12,6 +12,8 @@ import {
   createGamePDAs,
   setupEphemeralValidator,
   airdropToPlayers,
+  ensureConfig,
+  DEFAULT_GAME_OPTIONS,
 } from "./helpers";
 
 describe("04 - Gameplay: Turns & Initial Meld", () => {
@@ -29,11 +31,12 @@ describe("04 - Gameplay: Turns & Initial Meld", () => {
     console.log("Setting up game on ER...");
 
     ephemeralValidator = await setupEphemeralValidator(connection, isLocalnet);
+    await ensureConfig(program, connection, providerMagic);
     await airdropToPlayers(connection, isLocalnet, [player1, player2, player3]);
 
     // Initialize game
     let tx = await program.methods
-      .initializeGame(gameId, 3)
+      .initializeGame(gameId, 3, DEFAULT_GAME_OPTIONS, null)
       .accounts({
         game: gamePDA,
         authority: providerMagic.wallet.publicKey,
@@ -78,7 +81,7 @@ describe("04 - Gameplay: Turns & Initial Meld", () => {
     ];
 
     tx = await program.methods
-      .delegate()
+      .delegate({ manual: {} }, 0)
       .accounts({
         payer: providerMagic.wallet.publicKey,
         game: gamePDA,
@@ -13,6 +13,8 @@ import {
   createGamePDAs,
   setupEphemeralValidator,
   airdropToPlayers,
+  ensureConfig,
+  DEFAULT_GAME_OPTIONS,
 } from "./helpers";
 
 describe("05 - Commit & Undelegate", () => {
@@ -30,11 +32,12 @@ describe("05 - Commit & Undelegate", () => {
     console.log("Setting up game and delegating to ER...");
 
     ephemeralValidator = await setupEphemeralValidator(connection, isLocalnet);
+    await ensureConfig(program, connection, providerMagic);
     await airdropToPlayers(connection, isLocalnet, [player1, player2, player3]);
 
     // Initialize game
     let tx = await program.methods
-      .initializeGame(gameId, 3)
+      .initializeGame(gameId, 3, DEFAULT_GAME_OPTIONS, null)
       .accounts({
         game: gamePDA,
         authority: providerMagic.wallet.publicKey,
@@ -79,7 +82,7 @@ describe("05 - Commit & Undelegate", () => {
     ];
 
     tx = await program.methods
-      .delegate()
+      .delegate({ manual: {} }, 0)
       .accounts({
         payer: providerMagic.wallet.publicKey,
         game: gamePDA,
@@ -154,12 +157,20 @@ describe("05 - Commit & Undelegate", () => {
         payer: providerMagic.wallet.publicKey,
         game: gamePDA,
       })
+      // Mid-game undelegation needs a majority of the seated players
+      .remainingAccounts(
+        [player1, player2].map((player) => ({
+          pubkey: player.publicKey,
+          isSigner: true,
+          isWritable: false,
+        }))
+      )
       .transaction();
 
     const txHash = await sendAndConfirmTransaction(
       isLocalnet ? erConnection : connection,
       tx,
-      [providerMagic.wallet.payer],
+      [providerMagic.wallet.payer, player1, player2],
       {
         skipPreflight: true,
       }
@@ -12,6 +12,8 @@ import {
   createGamePDAs,
   setupEphemeralValidator,
   airdropToPlayers,
+  ensureConfig,
+  DEFAULT_GAME_OPTIONS,
 } from "./helpers";
 
 describe("06 - Winning & Prize Claiming", () => {
@@ -29,11 +31,12 @@ describe("06 - Winning & Prize Claiming", () => {
     console.log("Setting up game for win/claim testing...");
 
     ephemeralValidator = await setupEphemeralValidator(connection, isLocalnet);
+    await ensureConfig(program, connection, providerMagic);
     await airdropToPlayers(connection, isLocalnet, [player1, player2, player3]);
 
     // Initialize game
     let tx = await program.methods
-      .initializeGame(gameId, 3)
+      .initializeGame(gameId, 3, DEFAULT_GAME_OPTIONS, null)
       .accounts({
         game: gamePDA,
         authority: providerMagic.wallet.publicKey,
@@ -78,7 +81,7 @@ describe("06 - Winning & Prize Claiming", () => {
     ];
 
     tx = await program.methods
-      .delegate()
+      .delegate({ manual: {} }, 0)
       .accounts({
         payer: providerMagic.wallet.publicKey,
         game: gamePDA,
@@ -211,12 +214,20 @@ describe("06 - Winning & Prize Claiming", () => {
         payer: providerMagic.wallet.publicKey,
         game: gamePDA,
       })
+      // Mid-game undelegation needs a majority of the seated players
+      .remainingAccounts(
+        [player1, player2].map((player) => ({
+          pubkey: player.publicKey,
+          isSigner: true,
+          isWritable: false,
+        }))
+      )
       .transaction();
 
     await sendAndConfirmTransaction(
       isLocalnet ? erConnection : connection,
       tx,
-      [providerMagic.wallet.payer],
+      [providerMagic.wallet.payer, player1, player2],
       {
         skipPreflight: true,
       }
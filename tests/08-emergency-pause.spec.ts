@@ -0,0 +1,235 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { sendAndConfirmTransaction } from "@solana/web3.js";
+import { Rummikub } from "../target/types/rummikub";
+import {
+  setupConnections,
+  createTestPlayers,
+  createGamePDAs,
+  airdropToPlayers,
+  ensureConfig,
+  DEFAULT_GAME_OPTIONS,
+  ENTRY_FEE,
+} from "./helpers";
+import { assert } from "chai";
+
+describe("08 - Emergency Pause", () => {
+  const { connection, isLocalnet, providerMagic } = setupConnections();
+  const program: Program<Rummikub> = anchor.workspace.Rummikub;
+  const { player1, player2 } = createTestPlayers();
+  const gameId = new anchor.BN(Date.now());
+  const { gamePDA, escrowPDA } = createGamePDAs(program, gameId);
+
+  async function setPause(paused: boolean, playPaused: boolean) {
+    const tx = await program.methods
+      .setPause(paused, playPaused)
+      .accounts({
+        admin: providerMagic.wallet.publicKey,
+      })
+      .transaction();
+
+    await sendAndConfirmTransaction(
+      connection,
+      tx,
+      [providerMagic.wallet.payer],
+      {
+        skipPreflight: true,
+        commitment: "confirmed",
+      }
+    );
+  }
+
+  before(async function () {
+    console.log("\n========== SETUP ==========");
+    await ensureConfig(program, connection, providerMagic);
+    await airdropToPlayers(connection, isLocalnet, [player1, player2]);
+
+    // 2-player game, stays on base layer
+    let tx = await program.methods
+      .initializeGame(gameId, 2, DEFAULT_GAME_OPTIONS, null)
+      .accounts({
+        authority: providerMagic.wallet.publicKey,
+      })
+      .transaction();
+
+    await sendAndConfirmTransaction(
+      connection,
+      tx,
+      [providerMagic.wallet.payer],
+      {
+        skipPreflight: true,
+        commitment: "confirmed",
+      }
+    );
+
+    for (const player of [player1, player2]) {
+      tx = await program.methods
+        .joinGame()
+        .accounts({
+          game: gamePDA,
+          player: player.publicKey,
+        })
+        .transaction();
+
+      await sendAndConfirmTransaction(connection, tx, [player], {
+        skipPreflight: true,
+        commitment: "confirmed",
+      });
+    }
+    console.log("===========================\n");
+  });
+
+  after(async () => {
+    // Leave the program unpaused for the other suites
+    await setPause(false, false);
+  });
+
+  it("should not let a player cancel a running game while only joins are paused", async () => {
+    await setPause(true, false);
+
+    const tx = await program.methods
+      .emergencySettle()
+      .accounts({
+        game: gamePDA,
+        caller: player1.publicKey,
+      })
+      .remainingAccounts([
+        { pubkey: player1.publicKey, isSigner: false, isWritable: true },
+        { pubkey: player2.publicKey, isSigner: false, isWritable: true },
+      ])
+      .transaction();
+
+    try {
+      await sendAndConfirmTransaction(connection, tx, [player1], {
+        commitment: "confirmed",
+      });
+      assert.fail(
+        "emergency_settle should need play paused for a running game"
+      );
+    } catch (error) {
+      assert.include(error.toString(), "NotPaused");
+    }
+  });
+
+  it("should block new games while paused", async () => {
+    await setPause(true, true);
+
+    const blockedGameId = new anchor.BN(Date.now() + 1);
+    const tx = await program.methods
+      .initializeGame(blockedGameId, 2, DEFAULT_GAME_OPTIONS, null)
+      .accounts({
+        authority: providerMagic.wallet.publicKey,
+      })
+      .transaction();
+
+    try {
+      await sendAndConfirmTransaction(
+        connection,
+        tx,
+        [providerMagic.wallet.payer],
+        {
+          commitment: "confirmed",
+        }
+      );
+      assert.fail("initialize_game should fail while paused");
+    } catch (error) {
+      assert.include(error.toString(), "GamePaused");
+    }
+  });
+
+  it("should block turns when play is paused", async () => {
+    const gameState = await program.account.gameState.fetch(gamePDA);
+    const player = gameState.currentTurn === 0 ? player1 : player2;
+
+    const tx = await program.methods
+      .drawTile()
+      .accounts({
+        game: gamePDA,
+        player: player.publicKey,
+      })
+      .transaction();
+
+    try {
+      await sendAndConfirmTransaction(connection, tx, [player], {
+        commitment: "confirmed",
+      });
+      assert.fail("draw_tile should fail while play is paused");
+    } catch (error) {
+      assert.include(error.toString(), "PlayPaused");
+    }
+  });
+
+  it("should refund every player through emergency settle", async () => {
+    const before1 = await connection.getBalance(player1.publicKey);
+    const before2 = await connection.getBalance(player2.publicKey);
+
+    const tx = await program.methods
+      .emergencySettle()
+      .accounts({
+        game: gamePDA,
+        caller: providerMagic.wallet.publicKey,
+      })
+      .remainingAccounts([
+        { pubkey: player1.publicKey, isSigner: false, isWritable: true },
+        { pubkey: player2.publicKey, isSigner: false, isWritable: true },
+      ])
+      .transaction();
+
+    await sendAndConfirmTransaction(
+      connection,
+      tx,
+      [providerMagic.wallet.payer],
+      {
+        skipPreflight: true,
+        commitment: "confirmed",
+      }
+    );
+
+    const after1 = await connection.getBalance(player1.publicKey);
+    const after2 = await connection.getBalance(player2.publicKey);
+    assert.equal(after1 - before1, ENTRY_FEE);
+    assert.equal(after2 - before2, ENTRY_FEE);
+
+    const gameState = await program.account.gameState.fetch(gamePDA);
+    assert.deepEqual(gameState.gameStatus, { cancelled: {} });
+    assert.equal(gameState.prizePool.toNumber(), 0);
+  });
+
+  it("should close the cancelled game and return rent to the authority", async () => {
+    const rent =
+      (await connection.getBalance(gamePDA)) +
+      (await connection.getBalance(escrowPDA));
+    const authorityBefore = await connection.getBalance(
+      providerMagic.wallet.publicKey
+    );
+
+    const tx = await program.methods
+      .closeGame()
+      .accounts({
+        game: gamePDA,
+        authority: providerMagic.wallet.publicKey,
+      })
+      .transaction();
+
+    await sendAndConfirmTransaction(
+      connection,
+      tx,
+      [providerMagic.wallet.payer],
+      {
+        skipPreflight: true,
+        commitment: "confirmed",
+      }
+    );
+
+    const closed = await connection.getAccountInfo(gamePDA);
+    assert.isNull(closed, "Game account should be closed");
+    const closedEscrow = await connection.getAccountInfo(escrowPDA);
+    assert.isNull(closedEscrow, "Escrow account should be closed");
+
+    const authorityAfter = await connection.getBalance(
+      providerMagic.wallet.publicKey
+    );
+    // Rent comes back minus the transaction fee
+    assert.isAbove(authorityAfter, authorityBefore + rent - 10_000);
+  });
+});
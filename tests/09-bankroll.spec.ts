@@ -0,0 +1,202 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, web3 } from "@coral-xyz/anchor";
+import { LAMPORTS_PER_SOL, sendAndConfirmTransaction } from "@solana/web3.js";
+import { Rummikub } from "../target/types/rummikub";
+import {
+  setupConnections,
+  createTestPlayers,
+  createGamePDAs,
+  setupEphemeralValidator,
+  airdropToPlayers,
+  ensureConfig,
+  DEFAULT_GAME_OPTIONS,
+  getBankrollPDA,
+  ENTRY_FEE,
+} from "./helpers";
+import { assert } from "chai";
+
+describe("09 - Prepaid Bankroll", () => {
+  const { connection, erConnection, isLocalnet, providerMagic } =
+    setupConnections();
+  const program: Program<Rummikub> = anchor.workspace.Rummikub;
+  const { player1, player2 } = createTestPlayers();
+  const gameId = new anchor.BN(Date.now());
+  const { gamePDA } = createGamePDAs(program, gameId);
+  const DEPOSIT = 0.5 * LAMPORTS_PER_SOL;
+
+  let ephemeralValidator: any;
+
+  before(async function () {
+    console.log("\n========== SETUP ==========");
+    ephemeralValidator = await setupEphemeralValidator(connection, isLocalnet);
+    await ensureConfig(program, connection, providerMagic);
+    await airdropToPlayers(connection, isLocalnet, [player1, player2]);
+
+    const tx = await program.methods
+      .initializeGame(gameId, 2, DEFAULT_GAME_OPTIONS, null)
+      .accounts({
+        authority: providerMagic.wallet.publicKey,
+      })
+      .transaction();
+
+    await sendAndConfirmTransaction(
+      connection,
+      tx,
+      [providerMagic.wallet.payer],
+      {
+        skipPreflight: true,
+        commitment: "confirmed",
+      }
+    );
+    console.log("===========================\n");
+  });
+
+  it("should open and fund a bankroll for each player", async () => {
+    for (const player of [player1, player2]) {
+      const openIx = await program.methods
+        .openBankroll()
+        .accounts({
+          owner: player.publicKey,
+        })
+        .instruction();
+      const depositIx = await program.methods
+        .deposit(new anchor.BN(DEPOSIT))
+        .accounts({
+          owner: player.publicKey,
+        })
+        .instruction();
+
+      await sendAndConfirmTransaction(
+        connection,
+        new web3.Transaction().add(openIx, depositIx),
+        [player],
+        {
+          skipPreflight: true,
+          commitment: "confirmed",
+        }
+      );
+
+      const bankroll = await program.account.bankroll.fetch(
+        getBankrollPDA(program, player.publicKey)
+      );
+      assert.equal(bankroll.balance.toNumber(), DEPOSIT);
+      assert.equal(bankroll.locked.toNumber(), 0);
+    }
+  });
+
+  it("should withdraw unlocked funds on base layer", async () => {
+    const tx = await program.methods
+      .withdraw(new anchor.BN(ENTRY_FEE))
+      .accounts({
+        owner: player2.publicKey,
+      })
+      .transaction();
+
+    await sendAndConfirmTransaction(connection, tx, [player2], {
+      skipPreflight: true,
+      commitment: "confirmed",
+    });
+
+    const bankroll = await program.account.bankroll.fetch(
+      getBankrollPDA(program, player2.publicKey)
+    );
+    assert.equal(bankroll.balance.toNumber(), DEPOSIT - ENTRY_FEE);
+  });
+
+  it("should delegate the game and both bankrolls", async () => {
+    const remainingAccounts = [
+      {
+        pubkey: new web3.PublicKey(ephemeralValidator.identity),
+        isSigner: false,
+        isWritable: false,
+      },
+    ];
+
+    let tx = await program.methods
+      .delegate({ manual: {} }, 0)
+      .accounts({
+        payer: providerMagic.wallet.publicKey,
+        game: gamePDA,
+      })
+      .remainingAccounts(remainingAccounts)
+      .transaction();
+
+    await sendAndConfirmTransaction(
+      connection,
+      tx,
+      [providerMagic.wallet.payer],
+      {
+        skipPreflight: true,
+        commitment: "confirmed",
+      }
+    );
+
+    for (const player of [player1, player2]) {
+      tx = await program.methods
+        .delegateBankroll()
+        .accounts({
+          owner: player.publicKey,
+          bankroll: getBankrollPDA(program, player.publicKey),
+        })
+        .remainingAccounts(remainingAccounts)
+        .transaction();
+
+      await sendAndConfirmTransaction(connection, tx, [player], {
+        skipPreflight: true,
+        commitment: "confirmed",
+      });
+    }
+  });
+
+  it("should join inside the ER by debiting bankrolls", async () => {
+    for (const player of [player1, player2]) {
+      const tx = await program.methods
+        .joinGameWithBankroll()
+        .accounts({
+          game: gamePDA,
+          player: player.publicKey,
+        })
+        .transaction();
+
+      await sendAndConfirmTransaction(
+        isLocalnet ? erConnection : connection,
+        tx,
+        [player],
+        {
+          skipPreflight: true,
+        }
+      );
+    }
+
+    const gameState = await program.account.gameState.fetch(
+      gamePDA,
+      isLocalnet ? { commitment: "processed" } : undefined
+    );
+    assert.equal(gameState.currentPlayers, 2);
+    assert.isTrue(gameState.bankrollFunded);
+    assert.equal(gameState.prizePool.toNumber(), 2 * ENTRY_FEE);
+  });
+
+  it("should keep a bankroll with a locked stake in the ER", async () => {
+    const tx = await program.methods
+      .undelegateBankroll()
+      .accounts({
+        owner: player1.publicKey,
+      })
+      .transaction();
+
+    try {
+      await sendAndConfirmTransaction(
+        isLocalnet ? erConnection : connection,
+        tx,
+        [player1],
+        {
+          commitment: "confirmed",
+        }
+      );
+      assert.fail("undelegate_bankroll should fail while a stake is locked");
+    } catch (error) {
+      assert.include(error.toString(), "BankrollLocked");
+    }
+  });
+});
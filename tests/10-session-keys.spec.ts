@@ -0,0 +1,221 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, web3 } from "@coral-xyz/anchor";
+import {
+  Keypair,
+  LAMPORTS_PER_SOL,
+  sendAndConfirmTransaction,
+  SystemProgram,
+} from "@solana/web3.js";
+import { Rummikub } from "../target/types/rummikub";
+import {
+  setupConnections,
+  createTestPlayers,
+  createGamePDAs,
+  setupEphemeralValidator,
+  airdropToPlayers,
+  ensureConfig,
+  DEFAULT_GAME_OPTIONS,
+  getSessionPDA,
+} from "./helpers";
+import { assert } from "chai";
+
+describe("10 - Session Keys", () => {
+  const { connection, erConnection, isLocalnet, providerMagic } =
+    setupConnections();
+  const program: Program<Rummikub> = anchor.workspace.Rummikub;
+  const { player1, player2 } = createTestPlayers();
+  const gameId = new anchor.BN(Date.now());
+  const { gamePDA } = createGamePDAs(program, gameId);
+  const sessionKeys = [Keypair.generate(), Keypair.generate()];
+
+  const sessionPDA = (player: web3.PublicKey) =>
+    getSessionPDA(program, gamePDA, player);
+
+  let ephemeralValidator: any;
+
+  before(async function () {
+    console.log("\n========== SETUP ==========");
+    ephemeralValidator = await setupEphemeralValidator(connection, isLocalnet);
+    await ensureConfig(program, connection, providerMagic);
+    await airdropToPlayers(connection, isLocalnet, [player1, player2]);
+
+    let tx = await program.methods
+      .initializeGame(gameId, 2, DEFAULT_GAME_OPTIONS, null)
+      .accounts({
+        authority: providerMagic.wallet.publicKey,
+      })
+      .transaction();
+
+    await sendAndConfirmTransaction(
+      connection,
+      tx,
+      [providerMagic.wallet.payer],
+      {
+        skipPreflight: true,
+        commitment: "confirmed",
+      }
+    );
+
+    for (const player of [player1, player2]) {
+      tx = await program.methods
+        .joinGame()
+        .accounts({
+          game: gamePDA,
+          player: player.publicKey,
+        })
+        .transaction();
+
+      await sendAndConfirmTransaction(connection, tx, [player], {
+        skipPreflight: true,
+        commitment: "confirmed",
+      });
+    }
+
+    tx = await program.methods
+      .delegate({ manual: {} }, 0)
+      .accounts({
+        payer: providerMagic.wallet.publicKey,
+        game: gamePDA,
+      })
+      .remainingAccounts([
+        {
+          pubkey: new web3.PublicKey(ephemeralValidator.identity),
+          isSigner: false,
+          isWritable: false,
+        },
+      ])
+      .transaction();
+
+    await sendAndConfirmTransaction(
+      connection,
+      tx,
+      [providerMagic.wallet.payer],
+      {
+        skipPreflight: true,
+        commitment: "confirmed",
+      }
+    );
+    console.log("===========================\n");
+  });
+
+  it("should register a session key for each player", async () => {
+    for (const [i, player] of [player1, player2].entries()) {
+      const registerIx = await program.methods
+        .registerSession(sessionKeys[i].publicKey, new anchor.BN(3600))
+        .accounts({
+          game: gamePDA,
+          player: player.publicKey,
+        })
+        .instruction();
+      // Fund the session key so it can pay for its turns
+      const fundIx = SystemProgram.transfer({
+        fromPubkey: player.publicKey,
+        toPubkey: sessionKeys[i].publicKey,
+        lamports: 0.01 * LAMPORTS_PER_SOL,
+      });
+
+      await sendAndConfirmTransaction(
+        connection,
+        new web3.Transaction().add(registerIx, fundIx),
+        [player],
+        {
+          skipPreflight: true,
+          commitment: "confirmed",
+        }
+      );
+
+      const session = await program.account.sessionKey.fetch(
+        sessionPDA(player.publicKey)
+      );
+      assert.isTrue(session.player.equals(player.publicKey));
+      assert.isTrue(session.sessionKey.equals(sessionKeys[i].publicKey));
+    }
+  });
+
+  it("should draw a tile signed only by the session key", async () => {
+    const before = await program.account.gameState.fetch(
+      gamePDA,
+      isLocalnet ? { commitment: "processed" } : undefined
+    );
+    const seat = before.currentTurn;
+    const player = [player1, player2][seat];
+
+    const tx = await program.methods
+      .drawTile()
+      .accounts({
+        game: gamePDA,
+        player: sessionKeys[seat].publicKey,
+        session: sessionPDA(player.publicKey),
+      })
+      .transaction();
+
+    await sendAndConfirmTransaction(
+      isLocalnet ? erConnection : connection,
+      tx,
+      [sessionKeys[seat]],
+      {
+        skipPreflight: true,
+      }
+    );
+
+    const after = await program.account.gameState.fetch(
+      gamePDA,
+      isLocalnet ? { commitment: "processed" } : undefined
+    );
+    assert.equal(after.currentTurn, (seat + 1) % 2);
+    assert.equal(
+      after.players[seat].tileCount,
+      before.players[seat].tileCount + 1
+    );
+  });
+
+  it("should reject a session key used for another seat", async () => {
+    const gameState = await program.account.gameState.fetch(
+      gamePDA,
+      isLocalnet ? { commitment: "processed" } : undefined
+    );
+    const seat = gameState.currentTurn;
+    const player = [player1, player2][seat];
+    const otherKey = sessionKeys[(seat + 1) % 2];
+
+    const tx = await program.methods
+      .drawTile()
+      .accounts({
+        game: gamePDA,
+        player: otherKey.publicKey,
+        session: sessionPDA(player.publicKey),
+      })
+      .transaction();
+
+    try {
+      await sendAndConfirmTransaction(
+        isLocalnet ? erConnection : connection,
+        tx,
+        [otherKey]
+      );
+      assert.fail("A mismatched session key should be rejected");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidSessionKey");
+    }
+  });
+
+  it("should revoke a session key", async () => {
+    const tx = await program.methods
+      .revokeSession()
+      .accounts({
+        session: sessionPDA(player1.publicKey),
+        player: player1.publicKey,
+      })
+      .transaction();
+
+    await sendAndConfirmTransaction(connection, tx, [player1], {
+      skipPreflight: true,
+      commitment: "confirmed",
+    });
+
+    const closed = await connection.getAccountInfo(
+      sessionPDA(player1.publicKey)
+    );
+    assert.isNull(closed, "Session account should be closed");
+  });
+});
@@ -0,0 +1,346 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, web3 } from "@coral-xyz/anchor";
+import { sendAndConfirmTransaction } from "@solana/web3.js";
+import { Rummikub } from "../target/types/rummikub";
+import {
+  setupConnections,
+  createTestPlayers,
+  createGamePDAs,
+  setupEphemeralValidator,
+  airdropToPlayers,
+  ensureConfig,
+  getBankrollPDA,
+  DEFAULT_GAME_OPTIONS,
+  ENTRY_FEE,
+} from "./helpers";
+import { assert } from "chai";
+
+// The program only reads the last committed snapshot on base layer, so the
+// local ER validator can keep running: these games are simply never touched
+// there again. Stop the ER validator before this suite to reproduce a real
+// outage; the results are the same.
+describe("11 - Stalled Game Recovery", () => {
+  const { connection, isLocalnet, providerMagic } = setupConnections();
+  const program: Program<Rummikub> = anchor.workspace.Rummikub;
+  const { player1, player2 } = createTestPlayers();
+  const RECOVERY_TIMEOUT = 5; // seconds
+
+  const baseId = Date.now();
+  const resumeId = new anchor.BN(baseId);
+  const resumedAsId = new anchor.BN(baseId + 1);
+  const refundId = new anchor.BN(baseId + 2);
+  const scoreId = new anchor.BN(baseId + 3);
+  const bankrollId = new anchor.BN(baseId + 4);
+  const DEPOSIT = 0.5 * web3.LAMPORTS_PER_SOL;
+
+  let ephemeralValidator: any;
+
+  async function setRecoveryTimeout(seconds: number) {
+    const tx = await program.methods
+      .setRecoveryTimeout(new anchor.BN(seconds))
+      .accounts({
+        admin: providerMagic.wallet.publicKey,
+      })
+      .transaction();
+
+    await sendAndConfirmTransaction(
+      connection,
+      tx,
+      [providerMagic.wallet.payer],
+      {
+        skipPreflight: true,
+        commitment: "confirmed",
+      }
+    );
+  }
+
+  // Start a 2-player game on base layer and hand it to the ER. Bankroll games
+  // are joined from bankrolls that stay on base layer
+  async function createDelegatedGame(gameId: anchor.BN, fromBankroll = false) {
+    const { gamePDA } = createGamePDAs(program, gameId);
+
+    let tx = await program.methods
+      .initializeGame(gameId, 2, DEFAULT_GAME_OPTIONS, null)
+      .accounts({
+        authority: providerMagic.wallet.publicKey,
+      })
+      .transaction();
+
+    await sendAndConfirmTransaction(
+      connection,
+      tx,
+      [providerMagic.wallet.payer],
+      {
+        skipPreflight: true,
+        commitment: "confirmed",
+      }
+    );
+
+    for (const player of [player1, player2]) {
+      const join = fromBankroll
+        ? program.methods.joinGameWithBankroll()
+        : program.methods.joinGame();
+      tx = await join
+        .accounts({
+          game: gamePDA,
+          player: player.publicKey,
+        })
+        .transaction();
+
+      await sendAndConfirmTransaction(connection, tx, [player], {
+        skipPreflight: true,
+        commitment: "confirmed",
+      });
+    }
+
+    tx = await program.methods
+      .delegate({ manual: {} }, 0)
+      .accounts({
+        payer: providerMagic.wallet.publicKey,
+        game: gamePDA,
+      })
+      .remainingAccounts([
+        {
+          pubkey: new web3.PublicKey(ephemeralValidator.identity),
+          isSigner: false,
+          isWritable: false,
+        },
+      ])
+      .transaction();
+
+    await sendAndConfirmTransaction(
+      connection,
+      tx,
+      [providerMagic.wallet.payer],
+      {
+        skipPreflight: true,
+        commitment: "confirmed",
+      }
+    );
+  }
+
+  const wallets = [
+    { pubkey: player1.publicKey, isSigner: false, isWritable: true },
+    { pubkey: player2.publicKey, isSigner: false, isWritable: true },
+  ];
+
+  before(async function () {
+    console.log("\n========== SETUP ==========");
+    ephemeralValidator = await setupEphemeralValidator(connection, isLocalnet);
+    await ensureConfig(program, connection, providerMagic);
+    await airdropToPlayers(connection, isLocalnet, [player1, player2]);
+    await setRecoveryTimeout(RECOVERY_TIMEOUT);
+
+    for (const player of [player1, player2]) {
+      const openIx = await program.methods
+        .openBankroll()
+        .accounts({
+          owner: player.publicKey,
+        })
+        .instruction();
+      const depositIx = await program.methods
+        .deposit(new anchor.BN(DEPOSIT))
+        .accounts({
+          owner: player.publicKey,
+        })
+        .instruction();
+
+      await sendAndConfirmTransaction(
+        connection,
+        new web3.Transaction().add(openIx, depositIx),
+        [player],
+        {
+          skipPreflight: true,
+          commitment: "confirmed",
+        }
+      );
+    }
+
+    for (const gameId of [resumeId, refundId, scoreId]) {
+      await createDelegatedGame(gameId);
+    }
+    await createDelegatedGame(bankrollId, true);
+    console.log("===========================\n");
+  });
+
+  after(async () => {
+    // Restore the default for the other suites
+    await setRecoveryTimeout(60 * 60);
+  });
+
+  it("should refuse recovery before the timeout", async () => {
+    const { gamePDA, escrowPDA } = createGamePDAs(program, refundId);
+
+    const tx = await program.methods
+      .settleStalledGame({ refund: {} })
+      .accounts({
+        snapshot: gamePDA,
+        escrow: escrowPDA,
+        caller: player1.publicKey,
+      })
+      .remainingAccounts(wallets)
+      .transaction();
+
+    try {
+      await sendAndConfirmTransaction(connection, tx, [player1], {
+        commitment: "confirmed",
+      });
+      assert.fail("Recovery should wait for the timeout");
+    } catch (error) {
+      assert.include(error.toString(), "RecoveryTimeoutNotReached");
+    }
+
+    await new Promise((resolve) =>
+      setTimeout(resolve, (RECOVERY_TIMEOUT + 1) * 1000)
+    );
+  });
+
+  it("should resume a stalled game on base layer under a new id", async () => {
+    const { gamePDA, escrowPDA } = createGamePDAs(program, resumeId);
+    const resumed = createGamePDAs(program, resumedAsId);
+
+    const tx = await program.methods
+      .resumeGame(resumedAsId)
+      .accounts({
+        snapshot: gamePDA,
+        escrow: escrowPDA,
+        caller: player1.publicKey,
+      })
+      // Both players of a 2-player table make the majority
+      .remainingAccounts([
+        { pubkey: player2.publicKey, isSigner: true, isWritable: false },
+      ])
+      .transaction();
+
+    await sendAndConfirmTransaction(connection, tx, [player1, player2], {
+      skipPreflight: true,
+      commitment: "confirmed",
+    });
+
+    const gameState = await program.account.gameState.fetch(resumed.gamePDA);
+    assert.equal(gameState.gameId.toNumber(), resumedAsId.toNumber());
+    assert.equal(gameState.currentPlayers, 2);
+    assert.equal(gameState.prizePool.toNumber(), 2 * ENTRY_FEE);
+    assert.isTrue(gameState.players[0].pubkey.equals(player1.publicKey));
+
+    const oldEscrow = await program.account.escrow.fetch(escrowPDA);
+    assert.isTrue(oldEscrow.settled);
+  });
+
+  it("should not let one player void a stalled game", async () => {
+    const { gamePDA, escrowPDA } = createGamePDAs(program, refundId);
+
+    const tx = await program.methods
+      .settleStalledGame({ refund: {} })
+      .accounts({
+        snapshot: gamePDA,
+        escrow: escrowPDA,
+        caller: player2.publicKey,
+      })
+      .remainingAccounts(wallets)
+      .transaction();
+
+    try {
+      await sendAndConfirmTransaction(connection, tx, [player2], {
+        commitment: "confirmed",
+      });
+      assert.fail("A refund should need a majority of the table");
+    } catch (error) {
+      assert.include(error.toString(), "Unauthorized");
+    }
+  });
+
+  it("should refund a stalled game with a majority", async () => {
+    const { gamePDA, escrowPDA } = createGamePDAs(program, refundId);
+    const before1 = await connection.getBalance(player1.publicKey);
+    const before2 = await connection.getBalance(player2.publicKey);
+
+    const tx = await program.methods
+      .settleStalledGame({ refund: {} })
+      .accounts({
+        snapshot: gamePDA,
+        escrow: escrowPDA,
+        caller: player2.publicKey,
+      })
+      .remainingAccounts([
+        { ...wallets[0], isSigner: true },
+        wallets[1],
+      ])
+      .transaction();
+
+    await sendAndConfirmTransaction(connection, tx, [player2, player1], {
+      skipPreflight: true,
+      commitment: "confirmed",
+    });
+
+    const after1 = await connection.getBalance(player1.publicKey);
+    const after2 = await connection.getBalance(player2.publicKey);
+    assert.equal(after1 - before1, ENTRY_FEE);
+    // player2 paid the transaction fee
+    assert.isAbove(after2 - before2, ENTRY_FEE - 10_000);
+  });
+
+  it("should score the hands of a stalled game with a majority", async () => {
+    const { gamePDA, escrowPDA } = createGamePDAs(program, scoreId);
+
+    const tx = await program.methods
+      .settleStalledGame({ scoreHands: {} })
+      .accounts({
+        snapshot: gamePDA,
+        escrow: escrowPDA,
+        caller: player1.publicKey,
+      })
+      .remainingAccounts([
+        wallets[0],
+        { ...wallets[1], isSigner: true },
+      ])
+      .transaction();
+
+    await sendAndConfirmTransaction(connection, tx, [player1, player2], {
+      skipPreflight: true,
+      commitment: "confirmed",
+    });
+
+    const escrow = await program.account.escrow.fetch(escrowPDA);
+    assert.isTrue(escrow.settled);
+    const rent = await connection.getMinimumBalanceForRentExemption(
+      8 + 10 // discriminator + Escrow::SPACE
+    );
+    assert.equal(await connection.getBalance(escrowPDA), rent);
+  });
+
+  it("should unlock the stakes of a stalled bankroll game", async () => {
+    const { gamePDA, escrowPDA } = createGamePDAs(program, bankrollId);
+    const bankrolls = [player1, player2].map((player) => ({
+      pubkey: getBankrollPDA(program, player.publicKey),
+      isSigner: false,
+      isWritable: true,
+    }));
+
+    const tx = await program.methods
+      .settleStalledBankrollGame()
+      .accounts({
+        snapshot: gamePDA,
+        escrow: escrowPDA,
+        caller: player1.publicKey,
+      })
+      .remainingAccounts([
+        ...bankrolls,
+        { pubkey: player2.publicKey, isSigner: true, isWritable: false },
+      ])
+      .transaction();
+
+    await sendAndConfirmTransaction(connection, tx, [player1, player2], {
+      skipPreflight: true,
+      commitment: "confirmed",
+    });
+
+    for (const { pubkey } of bankrolls) {
+      const bankroll = await program.account.bankroll.fetch(pubkey);
+      assert.equal(bankroll.balance.toNumber(), DEPOSIT);
+      assert.equal(bankroll.locked.toNumber(), 0);
+    }
+    const escrow = await program.account.escrow.fetch(escrowPDA);
+    assert.isTrue(escrow.settled);
+  });
+});
@@ -0,0 +1,217 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, web3 } from "@coral-xyz/anchor";
+import { sendAndConfirmTransaction } from "@solana/web3.js";
+import { Rummikub } from "../target/types/rummikub";
+import {
+  setupConnections,
+  createTestPlayers,
+  createGamePDAs,
+  airdropToPlayers,
+  ensureConfig,
+  DEFAULT_GAME_OPTIONS,
+  getProfilePDA,
+} from "./helpers";
+import { assert } from "chai";
+
+describe("12 - Player Profiles", () => {
+  const { connection, isLocalnet, providerMagic } = setupConnections();
+  const program: Program<Rummikub> = anchor.workspace.Rummikub;
+  const { player1, player2 } = createTestPlayers();
+  const gameId = new anchor.BN(Date.now());
+  const { gamePDA } = createGamePDAs(program, gameId);
+
+  before(async function () {
+    console.log("\n========== SETUP ==========");
+    await ensureConfig(program, connection, providerMagic);
+    await airdropToPlayers(connection, isLocalnet, [player1, player2]);
+
+    const tx = await program.methods
+      .initializeGame(gameId, 2, DEFAULT_GAME_OPTIONS, null)
+      .accounts({
+        authority: providerMagic.wallet.publicKey,
+      })
+      .transaction();
+
+    await sendAndConfirmTransaction(
+      connection,
+      tx,
+      [providerMagic.wallet.payer],
+      {
+        skipPreflight: true,
+        commitment: "confirmed",
+      }
+    );
+    console.log("===========================\n");
+  });
+
+  it("should create and rename a profile", async () => {
+    const createIx = await program.methods
+      .createProfile("tile_shark")
+      .accounts({
+        owner: player1.publicKey,
+      })
+      .instruction();
+    const renameIx = await program.methods
+      .setDisplayName("Tile Shark")
+      .accounts({
+        owner: player1.publicKey,
+      })
+      .instruction();
+
+    await sendAndConfirmTransaction(
+      connection,
+      new web3.Transaction().add(createIx, renameIx),
+      [player1],
+      {
+        skipPreflight: true,
+        commitment: "confirmed",
+      }
+    );
+
+    const profile = await program.account.playerProfile.fetch(
+      getProfilePDA(program, player1.publicKey)
+    );
+    assert.equal(profile.displayName, "Tile Shark");
+    assert.equal(profile.gamesPlayed, 0);
+  });
+
+  it("should reject display names over 32 bytes", async () => {
+    const tx = await program.methods
+      .createProfile("x".repeat(33))
+      .accounts({
+        owner: player2.publicKey,
+      })
+      .transaction();
+
+    try {
+      await sendAndConfirmTransaction(connection, tx, [player2], {
+        commitment: "confirmed",
+      });
+      assert.fail("A long display name should be rejected");
+    } catch (error) {
+      assert.include(error.toString(), "DisplayNameTooLong");
+    }
+  });
+
+  it("should attach the profile when joining", async () => {
+    let tx = await program.methods
+      .joinGame()
+      .accounts({
+        game: gamePDA,
+        player: player1.publicKey,
+        profile: getProfilePDA(program, player1.publicKey),
+      })
+      .transaction();
+
+    await sendAndConfirmTransaction(connection, tx, [player1], {
+      skipPreflight: true,
+      commitment: "confirmed",
+    });
+
+    // Joining without a profile still works
+    tx = await program.methods
+      .joinGame()
+      .accounts({
+        game: gamePDA,
+        player: player2.publicKey,
+        profile: null,
+      })
+      .transaction();
+
+    await sendAndConfirmTransaction(connection, tx, [player2], {
+      skipPreflight: true,
+      commitment: "confirmed",
+    });
+
+    const gameState = await program.account.gameState.fetch(gamePDA);
+    assert.isTrue(gameState.players[0].hasProfile);
+    assert.isFalse(gameState.players[1].hasProfile);
+  });
+
+  it("should require a profile to join a rated game", async () => {
+    const ratedGameId = new anchor.BN(Date.now());
+    const rated = createGamePDAs(program, ratedGameId);
+
+    let tx = await program.methods
+      .initializeGame(
+        ratedGameId,
+        2,
+        { ...DEFAULT_GAME_OPTIONS, rated: true },
+        null
+      )
+      .accounts({
+        authority: providerMagic.wallet.publicKey,
+      })
+      .transaction();
+
+    await sendAndConfirmTransaction(
+      connection,
+      tx,
+      [providerMagic.wallet.payer],
+      {
+        skipPreflight: true,
+        commitment: "confirmed",
+      }
+    );
+
+    tx = await program.methods
+      .joinGame()
+      .accounts({
+        game: rated.gamePDA,
+        player: player2.publicKey,
+        profile: null,
+      })
+      .transaction();
+
+    try {
+      await sendAndConfirmTransaction(connection, tx, [player2], {
+        commitment: "confirmed",
+      });
+      assert.fail("Joining a rated game without a profile should fail");
+    } catch (error) {
+      assert.include(error.toString(), "ProfileRequired");
+    }
+
+    tx = await program.methods
+      .joinGame()
+      .accounts({
+        game: rated.gamePDA,
+        player: player1.publicKey,
+        profile: getProfilePDA(program, player1.publicKey),
+      })
+      .transaction();
+
+    await sendAndConfirmTransaction(connection, tx, [player1], {
+      skipPreflight: true,
+      commitment: "confirmed",
+    });
+
+    const gameState = await program.account.gameState.fetch(rated.gamePDA);
+    assert.isTrue(gameState.rated);
+    assert.equal(gameState.players[0].rating, 1200);
+  });
+
+  it("should only record results of finished games", async () => {
+    const tx = await program.methods
+      .recordResult()
+      .accounts({
+        game: gamePDA,
+        profile: getProfilePDA(program, player1.publicKey),
+      })
+      .transaction();
+
+    try {
+      await sendAndConfirmTransaction(
+        connection,
+        tx,
+        [providerMagic.wallet.payer],
+        {
+          commitment: "confirmed",
+        }
+      );
+      assert.fail("record_result should wait for the game to finish");
+    } catch (error) {
+      assert.include(error.toString(), "GameNotFinished");
+    }
+  });
+});
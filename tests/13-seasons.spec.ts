@@ -0,0 +1,145 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { LAMPORTS_PER_SOL, sendAndConfirmTransaction } from "@solana/web3.js";
+import { Rummikub } from "../target/types/rummikub";
+import {
+  setupConnections,
+  ensureConfig,
+  getConfigPDA,
+  getSeasonPDA,
+} from "./helpers";
+import { assert } from "chai";
+
+describe("13 - Seasons", () => {
+  const { connection, providerMagic } = setupConnections();
+  const program: Program<Rummikub> = anchor.workspace.Rummikub;
+  const seasonId = new anchor.BN(Date.now());
+  const seasonPDA = getSeasonPDA(program, seasonId);
+  const SEASON_LENGTH = 5; // seconds
+  const FUNDING = 0.2 * LAMPORTS_PER_SOL;
+
+  async function send(tx: anchor.web3.Transaction) {
+    await sendAndConfirmTransaction(
+      connection,
+      tx,
+      [providerMagic.wallet.payer],
+      {
+        skipPreflight: true,
+        commitment: "confirmed",
+      }
+    );
+  }
+
+  before(async function () {
+    console.log("\n========== SETUP ==========");
+    await ensureConfig(program, connection, providerMagic);
+    console.log("===========================\n");
+  });
+
+  after(async () => {
+    // Stop routing fees so the other suites can claim without a season
+    await send(
+      await program.methods
+        .setSeasonFee(0)
+        .accounts({
+          admin: providerMagic.wallet.publicKey,
+        })
+        .transaction()
+    );
+  });
+
+  it("should create an active season and route fees to it", async () => {
+    const now = Math.floor(Date.now() / 1000);
+
+    await send(
+      await program.methods
+        .createSeason(
+          seasonId,
+          new anchor.BN(now),
+          new anchor.BN(now + SEASON_LENGTH),
+          [5000, 3000, 2000]
+        )
+        .accounts({
+          admin: providerMagic.wallet.publicKey,
+        })
+        .transaction()
+    );
+    await send(
+      await program.methods
+        .setSeasonFee(2000)
+        .accounts({
+          admin: providerMagic.wallet.publicKey,
+        })
+        .transaction()
+    );
+
+    const config = await program.account.config.fetch(getConfigPDA(program));
+    assert.equal(config.activeSeason.toString(), seasonId.toString());
+    assert.equal(config.seasonFeeBps, 2000);
+
+    const season = await program.account.season.fetch(seasonPDA);
+    assert.deepEqual(season.payoutBps, [5000, 3000, 2000]);
+    assert.lengthOf(season.leaderboard, 0);
+  });
+
+  it("should fund the reward pool", async () => {
+    await send(
+      await program.methods
+        .fundSeason(new anchor.BN(FUNDING))
+        .accounts({
+          season: seasonPDA,
+          funder: providerMagic.wallet.publicKey,
+        })
+        .transaction()
+    );
+
+    const season = await program.account.season.fetch(seasonPDA);
+    assert.equal(season.rewardPool.toNumber(), FUNDING);
+  });
+
+  it("should not finalize before the season ends", async () => {
+    const tx = await program.methods
+      .finalizeSeason()
+      .accounts({
+        season: seasonPDA,
+      })
+      .transaction();
+
+    try {
+      await sendAndConfirmTransaction(
+        connection,
+        tx,
+        [providerMagic.wallet.payer],
+        {
+          commitment: "confirmed",
+        }
+      );
+      assert.fail("finalize_season should wait for end_ts");
+    } catch (error) {
+      assert.include(error.toString(), "SeasonNotEnded");
+    }
+  });
+
+  it("should finalize after the end and clear the active season", async () => {
+    await new Promise((resolve) =>
+      setTimeout(resolve, (SEASON_LENGTH + 1) * 1000)
+    );
+
+    // Nobody recorded a result, so the whole pool goes to the treasury
+    await send(
+      await program.methods
+        .finalizeSeason()
+        .accounts({
+          season: seasonPDA,
+        })
+        .transaction()
+    );
+
+    const season = await program.account.season.fetch(seasonPDA);
+    assert.isTrue(season.finalized);
+    assert.equal(season.rewardPool.toNumber(), 0);
+
+    const config = await program.account.config.fetch(getConfigPDA(program));
+    assert.equal(config.activeSeason.toNumber(), 0);
+  });
+});
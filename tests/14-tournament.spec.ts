@@ -0,0 +1,261 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { LAMPORTS_PER_SOL, sendAndConfirmTransaction } from "@solana/web3.js";
+import { Rummikub } from "../target/types/rummikub";
+import {
+  setupConnections,
+  createTestPlayers,
+  createGamePDAs,
+  airdropToPlayers,
+  ensureConfig,
+  getTournamentPDA,
+  getCounterPDA,
+  ensureGameCounter,
+} from "./helpers";
+import { assert } from "chai";
+
+describe("14 - Tournament", () => {
+  const { connection, isLocalnet, providerMagic } = setupConnections();
+  const program: Program<Rummikub> = anchor.workspace.Rummikub;
+  const { player1, player2, player3 } = createTestPlayers();
+  const tournamentId = new anchor.BN(Date.now());
+  const tournamentPDA = getTournamentPDA(program, tournamentId);
+  // Assigned from the game counter when the table game is created
+  let tableGameId: anchor.BN;
+  const BUY_IN = 0.05 * LAMPORTS_PER_SOL;
+
+  before(async function () {
+    console.log("\n========== SETUP ==========");
+    await ensureConfig(program, connection, providerMagic);
+    await ensureGameCounter(program, connection, providerMagic);
+    await airdropToPlayers(connection, isLocalnet, [
+      player1,
+      player2,
+      player3,
+    ]);
+
+    const tx = await program.methods
+      .createTournament(
+        tournamentId,
+        { singleElimination: {} },
+        2,
+        new anchor.BN(BUY_IN),
+        3,
+        [7000, 2500]
+      )
+      .accounts({
+        authority: providerMagic.wallet.publicKey,
+      })
+      .transaction();
+
+    await sendAndConfirmTransaction(
+      connection,
+      tx,
+      [providerMagic.wallet.payer],
+      {
+        skipPreflight: true,
+        commitment: "confirmed",
+      }
+    );
+    console.log("===========================\n");
+  });
+
+  it("should collect buy-ins at registration", async () => {
+    for (const player of [player1, player2, player3]) {
+      const tx = await program.methods
+        .registerTournament()
+        .accounts({
+          tournament: tournamentPDA,
+          player: player.publicKey,
+        })
+        .transaction();
+
+      await sendAndConfirmTransaction(connection, tx, [player], {
+        skipPreflight: true,
+        commitment: "confirmed",
+      });
+    }
+
+    const tournament = await program.account.tournament.fetch(tournamentPDA);
+    assert.lengthOf(tournament.entrants, 3);
+    assert.equal(tournament.prizePool.toNumber(), 3 * BUY_IN);
+  });
+
+  it("should seat round 1 with a bye for the odd player", async () => {
+    const tx = await program.methods
+      .startRound()
+      .accounts({
+        tournament: tournamentPDA,
+        authority: providerMagic.wallet.publicKey,
+      })
+      .transaction();
+
+    await sendAndConfirmTransaction(
+      connection,
+      tx,
+      [providerMagic.wallet.payer],
+      {
+        skipPreflight: true,
+        commitment: "confirmed",
+      }
+    );
+
+    const tournament = await program.account.tournament.fetch(tournamentPDA);
+    assert.equal(tournament.currentRound, 1);
+    assert.lengthOf(tournament.tables, 2);
+    assert.equal(tournament.tables[0].size, 2);
+    assert.equal(tournament.tables[1].size, 1);
+    // The bye is decided immediately
+    assert.equal(tournament.tables[1].winner, tournament.tables[1].first);
+  });
+
+  it("should create the table game seated and started", async () => {
+    const counter = await program.account.gameCounter.fetch(
+      getCounterPDA(program)
+    );
+    tableGameId = counter.nextGameId;
+    const { gamePDA } = createGamePDAs(program, tableGameId);
+
+    const tx = await program.methods
+      .createTableGame(0)
+      .accounts({
+        tournament: tournamentPDA,
+        game: gamePDA,
+        payer: providerMagic.wallet.publicKey,
+      })
+      .transaction();
+
+    await sendAndConfirmTransaction(
+      connection,
+      tx,
+      [providerMagic.wallet.payer],
+      {
+        skipPreflight: true,
+        commitment: "confirmed",
+      }
+    );
+
+    const gameState = await program.account.gameState.fetch(gamePDA);
+    assert.deepEqual(gameState.gameStatus, { inProgress: {} });
+    assert.equal(gameState.currentPlayers, 2);
+    assert.isTrue(gameState.tournament.equals(tournamentPDA));
+    assert.isTrue(gameState.players[0].pubkey.equals(player1.publicKey));
+    assert.isTrue(gameState.players[1].pubkey.equals(player2.publicKey));
+
+    const tournament = await program.account.tournament.fetch(tournamentPDA);
+    assert.equal(
+      tournament.tables[0].gameId.toString(),
+      tableGameId.toString()
+    );
+  });
+
+  it("should not report a table that is still being played", async () => {
+    const { gamePDA } = createGamePDAs(program, tableGameId);
+
+    const tx = await program.methods
+      .reportTable()
+      .accounts({
+        tournament: tournamentPDA,
+        game: gamePDA,
+      })
+      .transaction();
+
+    try {
+      await sendAndConfirmTransaction(
+        connection,
+        tx,
+        [providerMagic.wallet.payer],
+        {
+          commitment: "confirmed",
+        }
+      );
+      assert.fail("report_table should wait for the game to finish");
+    } catch (error) {
+      assert.include(error.toString(), "GameNotFinished");
+    }
+  });
+
+  it("should refund buy-ins when a tournament is cancelled", async () => {
+    const cancelledId = new anchor.BN(Date.now() + 2000);
+    const cancelledPDA = getTournamentPDA(program, cancelledId);
+
+    const createTx = await program.methods
+      .createTournament(
+        cancelledId,
+        { singleElimination: {} },
+        2,
+        new anchor.BN(BUY_IN),
+        4,
+        [10000]
+      )
+      .accounts({
+        authority: providerMagic.wallet.publicKey,
+      })
+      .transaction();
+    await sendAndConfirmTransaction(
+      connection,
+      createTx,
+      [providerMagic.wallet.payer],
+      {
+        commitment: "confirmed",
+      }
+    );
+
+    const registerTx = await program.methods
+      .registerTournament()
+      .accounts({
+        tournament: cancelledPDA,
+        player: player1.publicKey,
+      })
+      .transaction();
+    await sendAndConfirmTransaction(connection, registerTx, [player1], {
+      commitment: "confirmed",
+    });
+
+    // Registration has only just opened, so only the organizer may cancel
+    const earlyTx = await program.methods
+      .cancelTournament()
+      .accounts({
+        tournament: cancelledPDA,
+        caller: player1.publicKey,
+      })
+      .remainingAccounts([
+        { pubkey: player1.publicKey, isSigner: false, isWritable: true },
+      ])
+      .transaction();
+    try {
+      await sendAndConfirmTransaction(connection, earlyTx, [player1], {
+        commitment: "confirmed",
+      });
+      assert.fail("cancel_tournament should wait for the window to pass");
+    } catch (error) {
+      assert.include(error.toString(), "RegistrationStillOpen");
+    }
+
+    const balanceBefore = await connection.getBalance(player1.publicKey);
+    const cancelTx = await program.methods
+      .cancelTournament()
+      .accounts({
+        tournament: cancelledPDA,
+        caller: providerMagic.wallet.publicKey,
+      })
+      .remainingAccounts([
+        { pubkey: player1.publicKey, isSigner: false, isWritable: true },
+      ])
+      .transaction();
+    await sendAndConfirmTransaction(
+      connection,
+      cancelTx,
+      [providerMagic.wallet.payer],
+      {
+        commitment: "confirmed",
+      }
+    );
+
+    const tournament = await program.account.tournament.fetch(cancelledPDA);
+    assert.deepEqual(tournament.status, { cancelled: {} });
+    assert.equal(tournament.prizePool.toNumber(), 0);
+    const balanceAfter = await connection.getBalance(player1.publicKey);
+    assert.equal(balanceAfter - balanceBefore, BUY_IN);
+  });
+});
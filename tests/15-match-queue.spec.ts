@@ -0,0 +1,163 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { LAMPORTS_PER_SOL, sendAndConfirmTransaction } from "@solana/web3.js";
+import { Rummikub } from "../target/types/rummikub";
+import {
+  setupConnections,
+  createTestPlayers,
+  createGamePDAs,
+  airdropToPlayers,
+  ensureConfig,
+  getQueuePDA,
+} from "./helpers";
+import { assert } from "chai";
+
+describe("15 - Matchmaking Queue", () => {
+  const { connection, isLocalnet, providerMagic } = setupConnections();
+  const program: Program<Rummikub> = anchor.workspace.Rummikub;
+  const { player1, player2, player3 } = createTestPlayers();
+  // A stake no other suite uses, so the queue starts empty
+  const stake = new anchor.BN(0.02 * LAMPORTS_PER_SOL + (Date.now() % 1000));
+  const queuePDA = getQueuePDA(program, stake, 2);
+  const gameId = new anchor.BN(Date.now());
+  const { gamePDA, escrowPDA } = createGamePDAs(program, gameId);
+
+  async function enqueue(player: anchor.web3.Keypair) {
+    const tx = await program.methods
+      .enqueue()
+      .accounts({
+        queue: queuePDA,
+        player: player.publicKey,
+        profile: null,
+      })
+      .transaction();
+
+    await sendAndConfirmTransaction(connection, tx, [player], {
+      skipPreflight: true,
+      commitment: "confirmed",
+    });
+  }
+
+  before(async function () {
+    console.log("\n========== SETUP ==========");
+    await ensureConfig(program, connection, providerMagic);
+    await airdropToPlayers(connection, isLocalnet, [
+      player1,
+      player2,
+      player3,
+    ]);
+
+    const tx = await program.methods
+      .openQueue(stake, 2, 0)
+      .accounts({
+        admin: providerMagic.wallet.publicKey,
+      })
+      .transaction();
+
+    await sendAndConfirmTransaction(
+      connection,
+      tx,
+      [providerMagic.wallet.payer],
+      {
+        skipPreflight: true,
+        commitment: "confirmed",
+      }
+    );
+    console.log("===========================\n");
+  });
+
+  it("should hold stakes while players wait", async () => {
+    for (const player of [player1, player2, player3]) {
+      await enqueue(player);
+    }
+
+    const queue = await program.account.matchQueue.fetch(queuePDA);
+    assert.lengthOf(queue.waiting, 3);
+  });
+
+  it("should refund a player who leaves the queue", async () => {
+    const before = await connection.getBalance(player2.publicKey);
+
+    const tx = await program.methods
+      .leaveQueue()
+      .accounts({
+        queue: queuePDA,
+        player: player2.publicKey,
+      })
+      .transaction();
+
+    await sendAndConfirmTransaction(connection, tx, [player2], {
+      skipPreflight: true,
+      commitment: "confirmed",
+    });
+
+    const after = await connection.getBalance(player2.publicKey);
+    assert.isAbove(after, before + stake.toNumber() - 10_000);
+
+    const queue = await program.account.matchQueue.fetch(queuePDA);
+    assert.lengthOf(queue.waiting, 2);
+  });
+
+  it("should seat queued players and start a game", async () => {
+    const tx = await program.methods
+      .matchPlayers(gameId)
+      .accounts({
+        queue: queuePDA,
+        cranker: providerMagic.wallet.publicKey,
+      })
+      .transaction();
+
+    await sendAndConfirmTransaction(
+      connection,
+      tx,
+      [providerMagic.wallet.payer],
+      {
+        skipPreflight: true,
+        commitment: "confirmed",
+      }
+    );
+
+    const gameState = await program.account.gameState.fetch(gamePDA);
+    assert.deepEqual(gameState.gameStatus, { inProgress: {} });
+    assert.equal(gameState.currentPlayers, 2);
+    assert.equal(gameState.entryFee.toNumber(), stake.toNumber());
+    assert.equal(gameState.prizePool.toNumber(), 2 * stake.toNumber());
+    assert.isTrue(gameState.players[0].pubkey.equals(player1.publicKey));
+    assert.isTrue(gameState.players[1].pubkey.equals(player3.publicKey));
+
+    const escrowRent = await connection.getMinimumBalanceForRentExemption(
+      8 + 10
+    );
+    const escrowBalance = await connection.getBalance(escrowPDA);
+    assert.equal(escrowBalance, escrowRent + 2 * stake.toNumber());
+
+    const queue = await program.account.matchQueue.fetch(queuePDA);
+    assert.lengthOf(queue.waiting, 0);
+  });
+
+  it("should not match without enough players", async () => {
+    await enqueue(player2);
+
+    const tx = await program.methods
+      .matchPlayers(new anchor.BN(Date.now() + 1))
+      .accounts({
+        queue: queuePDA,
+        cranker: providerMagic.wallet.publicKey,
+      })
+      .transaction();
+
+    try {
+      await sendAndConfirmTransaction(
+        connection,
+        tx,
+        [providerMagic.wallet.payer],
+        {
+          commitment: "confirmed",
+        }
+      );
+      assert.fail("match_players should fail with one player queued");
+    } catch (error) {
+      assert.include(error.toString(), "NotEnoughPlayers");
+    }
+  });
+});
@@ -0,0 +1,172 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { sendAndConfirmTransaction } from "@solana/web3.js";
+import { Rummikub } from "../target/types/rummikub";
+import {
+  setupConnections,
+  createTestPlayers,
+  createGamePDAs,
+  airdropToPlayers,
+  ensureConfig,
+  DEFAULT_GAME_OPTIONS,
+  ENTRY_FEE,
+  getLobbyPDA,
+} from "./helpers";
+import { assert } from "chai";
+
+describe("16 - Open-Game Registry", () => {
+  const { connection, isLocalnet, providerMagic } = setupConnections();
+  const program: Program<Rummikub> = anchor.workspace.Rummikub;
+  const { player1, player2 } = createTestPlayers();
+  // A fresh page per run keeps the suite independent of earlier runs
+  const page = Date.now() % 1_000_000;
+  const lobbyPDA = getLobbyPDA(program, page);
+  const gameId = new anchor.BN(Date.now());
+  const { gamePDA } = createGamePDAs(program, gameId);
+
+  before(async function () {
+    console.log("\n========== SETUP ==========");
+    await ensureConfig(program, connection, providerMagic);
+    await airdropToPlayers(connection, isLocalnet, [player1, player2]);
+
+    const tx = await program.methods
+      .openLobbyPage(page)
+      .accounts({
+        payer: providerMagic.wallet.publicKey,
+      })
+      .transaction();
+
+    await sendAndConfirmTransaction(
+      connection,
+      tx,
+      [providerMagic.wallet.payer],
+      {
+        skipPreflight: true,
+        commitment: "confirmed",
+      }
+    );
+    console.log("===========================\n");
+  });
+
+  it("should list a new game", async () => {
+    const tx = await program.methods
+      .initializeGame(gameId, 2, DEFAULT_GAME_OPTIONS, null)
+      .accounts({
+        authority: providerMagic.wallet.publicKey,
+        lobby: lobbyPDA,
+      })
+      .transaction();
+
+    await sendAndConfirmTransaction(
+      connection,
+      tx,
+      [providerMagic.wallet.payer],
+      {
+        skipPreflight: true,
+        commitment: "confirmed",
+      }
+    );
+
+    const lobby = await program.account.lobbyPage.fetch(lobbyPDA);
+    assert.lengthOf(lobby.entries, 1);
+    assert.equal(lobby.entries[0].gameId.toString(), gameId.toString());
+    assert.equal(lobby.entries[0].stake.toNumber(), ENTRY_FEE);
+    assert.equal(lobby.entries[0].seatsLeft, 2);
+    assert.isFalse(lobby.entries[0].options.rated);
+    assert.isFalse(lobby.entries[0].private);
+  });
+
+  it("should reject a join that leaves the registry behind", async () => {
+    const tx = await program.methods
+      .joinGame()
+      .accounts({
+        game: gamePDA,
+        player: player1.publicKey,
+      })
+      .transaction();
+
+    try {
+      await sendAndConfirmTransaction(connection, tx, [player1], {
+        commitment: "confirmed",
+      });
+      assert.fail("join_game should require the game's lobby page");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidLobbyPage");
+    }
+  });
+
+  it("should update seats left and drop the game once full", async () => {
+    for (const [i, player] of [player1, player2].entries()) {
+      const tx = await program.methods
+        .joinGame()
+        .accounts({
+          game: gamePDA,
+          player: player.publicKey,
+          lobby: lobbyPDA,
+        })
+        .transaction();
+
+      await sendAndConfirmTransaction(connection, tx, [player], {
+        skipPreflight: true,
+        commitment: "confirmed",
+      });
+
+      const lobby = await program.account.lobbyPage.fetch(lobbyPDA);
+      if (i === 0) {
+        assert.equal(lobby.entries[0].seatsLeft, 1);
+      } else {
+        assert.lengthOf(lobby.entries, 0);
+      }
+    }
+
+    const gameState = await program.account.gameState.fetch(gamePDA);
+    assert.deepEqual(gameState.gameStatus, { inProgress: {} });
+  });
+
+  it("should keep entries for games still waiting when pruned", async () => {
+    const waitingId = new anchor.BN(Date.now() + 1);
+    const waiting = createGamePDAs(program, waitingId);
+
+    let tx = await program.methods
+      .initializeGame(waitingId, 2, DEFAULT_GAME_OPTIONS, null)
+      .accounts({
+        authority: providerMagic.wallet.publicKey,
+        lobby: lobbyPDA,
+      })
+      .transaction();
+
+    await sendAndConfirmTransaction(
+      connection,
+      tx,
+      [providerMagic.wallet.payer],
+      {
+        skipPreflight: true,
+        commitment: "confirmed",
+      }
+    );
+
+    tx = await program.methods
+      .pruneLobby([waitingId])
+      .accounts({
+        lobby: lobbyPDA,
+      })
+      .remainingAccounts([
+        { pubkey: waiting.gamePDA, isSigner: false, isWritable: false },
+      ])
+      .transaction();
+
+    await sendAndConfirmTransaction(
+      connection,
+      tx,
+      [providerMagic.wallet.payer],
+      {
+        skipPreflight: true,
+        commitment: "confirmed",
+      }
+    );
+
+    const lobby = await program.account.lobbyPage.fetch(lobbyPDA);
+    assert.lengthOf(lobby.entries, 1);
+    assert.equal(lobby.entries[0].seatsLeft, 2);
+  });
+});
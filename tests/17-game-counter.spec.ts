@@ -0,0 +1,94 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { sendAndConfirmTransaction } from "@solana/web3.js";
+import { Rummikub } from "../target/types/rummikub";
+import {
+  setupConnections,
+  createGamePDAs,
+  ensureConfig,
+  DEFAULT_GAME_OPTIONS,
+  getCounterPDA,
+  ensureGameCounter,
+} from "./helpers";
+import { assert } from "chai";
+
+describe("17 - Game Counter", () => {
+  const { connection, providerMagic } = setupConnections();
+  const program: Program<Rummikub> = anchor.workspace.Rummikub;
+  const counterPDA = getCounterPDA(program);
+
+  before(async function () {
+    console.log("\n========== SETUP ==========");
+    await ensureConfig(program, connection, providerMagic);
+    await ensureGameCounter(program, connection, providerMagic);
+    console.log("===========================\n");
+  });
+
+  it("should create games under consecutive counter ids", async () => {
+    const counter = await program.account.gameCounter.fetch(counterPDA);
+    const firstId: anchor.BN = counter.nextGameId;
+
+    for (let i = 0; i < 2; i++) {
+      const expectedId = firstId.addn(i);
+      const { gamePDA } = createGamePDAs(program, expectedId);
+
+      const returned = await program.methods
+        .initializeNextGame(2, DEFAULT_GAME_OPTIONS, null)
+        .accounts({
+          authority: providerMagic.wallet.publicKey,
+          game: gamePDA,
+        })
+        .view();
+      assert.equal(returned.toString(), expectedId.toString());
+
+      const tx = await program.methods
+        .initializeNextGame(2, DEFAULT_GAME_OPTIONS, null)
+        .accounts({
+          authority: providerMagic.wallet.publicKey,
+          game: gamePDA,
+        })
+        .transaction();
+
+      await sendAndConfirmTransaction(
+        connection,
+        tx,
+        [providerMagic.wallet.payer],
+        {
+          skipPreflight: true,
+          commitment: "confirmed",
+        }
+      );
+
+      const gameState = await program.account.gameState.fetch(gamePDA);
+      assert.equal(gameState.gameId.toString(), expectedId.toString());
+    }
+
+    const after = await program.account.gameCounter.fetch(counterPDA);
+    assert.equal(after.nextGameId.toString(), firstId.addn(2).toString());
+  });
+
+  it("should keep caller-chosen ids out of the counter range", async () => {
+    const reservedId = new anchor.BN(1).shln(63);
+
+    const tx = await program.methods
+      .initializeGame(reservedId, 2, DEFAULT_GAME_OPTIONS, null)
+      .accounts({
+        authority: providerMagic.wallet.publicKey,
+      })
+      .transaction();
+
+    try {
+      await sendAndConfirmTransaction(
+        connection,
+        tx,
+        [providerMagic.wallet.payer],
+        {
+          commitment: "confirmed",
+        }
+      );
+      assert.fail("initialize_game should reject ids from the counter range");
+    } catch (error) {
+      assert.include(error.toString(), "ReservedGameId");
+    }
+  });
+});
@@ -0,0 +1,144 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { sendAndConfirmTransaction } from "@solana/web3.js";
+import { createHash } from "crypto";
+import { Rummikub } from "../target/types/rummikub";
+import {
+  setupConnections,
+  createTestPlayers,
+  createGamePDAs,
+  airdropToPlayers,
+  ensureConfig,
+  DEFAULT_GAME_OPTIONS,
+  ENTRY_FEE,
+} from "./helpers";
+import { assert } from "chai";
+
+describe("18 - Private Games", () => {
+  const { connection, isLocalnet, providerMagic } = setupConnections();
+  const program: Program<Rummikub> = anchor.workspace.Rummikub;
+  const { player1, player2, player3 } = createTestPlayers();
+  const gameId = new anchor.BN(Date.now());
+  const { gamePDA } = createGamePDAs(program, gameId);
+  const PASSWORD = Buffer.from("tiles");
+
+  async function join(player: anchor.web3.Keypair, password?: Buffer) {
+    const method = password
+      ? program.methods.joinGameWithPassword(password)
+      : program.methods.joinGame();
+    const tx = await method
+      .accounts({
+        game: gamePDA,
+        player: player.publicKey,
+      })
+      .transaction();
+
+    await sendAndConfirmTransaction(connection, tx, [player], {
+      commitment: "confirmed",
+    });
+  }
+
+  async function expectJoinError(
+    player: anchor.web3.Keypair,
+    password: Buffer | undefined,
+    error: string
+  ) {
+    try {
+      await join(player, password);
+      assert.fail(`join should fail with ${error}`);
+    } catch (e) {
+      assert.include(e.toString(), error);
+    }
+  }
+
+  before(async function () {
+    console.log("\n========== SETUP ==========");
+    await ensureConfig(program, connection, providerMagic);
+    await airdropToPlayers(connection, isLocalnet, [
+      player1,
+      player2,
+      player3,
+    ]);
+
+    // Password protected, with one seat held for player3
+    const passwordHash = createHash("sha256")
+      .update(gameId.toArrayLike(Buffer, "le", 8))
+      .update(PASSWORD)
+      .digest();
+
+    const tx = await program.methods
+      .initializeGame(gameId, 2, DEFAULT_GAME_OPTIONS, {
+        allowlist: [],
+        passwordHash: [...passwordHash],
+        reserved: [player3.publicKey],
+      })
+      .accounts({
+        authority: providerMagic.wallet.publicKey,
+      })
+      .transaction();
+
+    await sendAndConfirmTransaction(
+      connection,
+      tx,
+      [providerMagic.wallet.payer],
+      {
+        skipPreflight: true,
+        commitment: "confirmed",
+      }
+    );
+    console.log("===========================\n");
+  });
+
+  it("should require the password", async () => {
+    await expectJoinError(player1, undefined, "InvalidPassword");
+    await expectJoinError(player1, Buffer.from("rummy"), "InvalidPassword");
+    await join(player1, PASSWORD);
+
+    const gameState = await program.account.gameState.fetch(gamePDA);
+    assert.equal(gameState.currentPlayers, 1);
+  });
+
+  it("should hold the reserved seat for the invited player", async () => {
+    await expectJoinError(player2, PASSWORD, "SeatsReserved");
+  });
+
+  it("should let the authority kick a player with a refund", async () => {
+    const before = await connection.getBalance(player1.publicKey);
+
+    const tx = await program.methods
+      .kickPlayer()
+      .accounts({
+        game: gamePDA,
+        authority: providerMagic.wallet.publicKey,
+        player: player1.publicKey,
+      })
+      .transaction();
+
+    await sendAndConfirmTransaction(
+      connection,
+      tx,
+      [providerMagic.wallet.payer],
+      {
+        skipPreflight: true,
+        commitment: "confirmed",
+      }
+    );
+
+    const after = await connection.getBalance(player1.publicKey);
+    assert.equal(after - before, ENTRY_FEE);
+
+    const gameState = await program.account.gameState.fetch(gamePDA);
+    assert.equal(gameState.currentPlayers, 0);
+    assert.equal(gameState.prizePool.toNumber(), 0);
+    assert.equal(gameState.tilesRemaining, 106);
+  });
+
+  it("should seat the invited player without the password", async () => {
+    await join(player3);
+    await join(player2, PASSWORD);
+
+    const gameState = await program.account.gameState.fetch(gamePDA);
+    assert.deepEqual(gameState.gameStatus, { inProgress: {} });
+    assert.isTrue(gameState.players[0].pubkey.equals(player3.publicKey));
+  });
+});
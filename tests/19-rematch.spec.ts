@@ -0,0 +1,98 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { sendAndConfirmTransaction } from "@solana/web3.js";
+import { Rummikub } from "../target/types/rummikub";
+import {
+  setupConnections,
+  createTestPlayers,
+  createGamePDAs,
+  airdropToPlayers,
+  ensureConfig,
+  DEFAULT_GAME_OPTIONS,
+} from "./helpers";
+import { assert } from "chai";
+
+describe("19 - Rematch", () => {
+  const { connection, isLocalnet, providerMagic } = setupConnections();
+  const program: Program<Rummikub> = anchor.workspace.Rummikub;
+  const { player1, player2 } = createTestPlayers();
+  const gameId = new anchor.BN(Date.now());
+  const { gamePDA } = createGamePDAs(program, gameId);
+
+  before(async function () {
+    console.log("\n========== SETUP ==========");
+    await ensureConfig(program, connection, providerMagic);
+    await airdropToPlayers(connection, isLocalnet, [player1, player2]);
+
+    let tx = await program.methods
+      .initializeGame(gameId, 2, DEFAULT_GAME_OPTIONS, null)
+      .accounts({
+        authority: providerMagic.wallet.publicKey,
+      })
+      .transaction();
+
+    await sendAndConfirmTransaction(
+      connection,
+      tx,
+      [providerMagic.wallet.payer],
+      {
+        skipPreflight: true,
+        commitment: "confirmed",
+      }
+    );
+
+    for (const player of [player1, player2]) {
+      tx = await program.methods
+        .joinGame()
+        .accounts({
+          game: gamePDA,
+          player: player.publicKey,
+        })
+        .transaction();
+
+      await sendAndConfirmTransaction(connection, tx, [player], {
+        skipPreflight: true,
+        commitment: "confirmed",
+      });
+    }
+    console.log("===========================\n");
+  });
+
+  it("should refuse a rematch while the game is running", async () => {
+    const tx = await program.methods
+      .rematch()
+      .accounts({
+        game: gamePDA,
+      })
+      .remainingAccounts([
+        { pubkey: player1.publicKey, isSigner: true, isWritable: true },
+        { pubkey: player2.publicKey, isSigner: true, isWritable: true },
+      ])
+      .transaction();
+
+    try {
+      await sendAndConfirmTransaction(connection, tx, [player1, player2], {
+        commitment: "confirmed",
+      });
+      assert.fail("rematch should fail before the game is finished");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidGameState");
+    }
+
+    const gameState = await program.account.gameState.fetch(gamePDA);
+    assert.deepEqual(gameState.gameStatus, { inProgress: {} });
+  });
+
+  it("should document the rematch flow", async () => {
+    console.log("\n=== REMATCH FLOW ===");
+    console.log("1. The game finishes and the winner calls claim_prize");
+    console.log("2. Players with profiles get their result recorded");
+    console.log("3. Everyone who wants another round signs one rematch tx,");
+    console.log("   passing their wallets as remaining accounts, each");
+    console.log("   followed by its profile if they played with one");
+    console.log("4. Each pays a new entry fee; racks, table and pool reset");
+    console.log("5. Seats rotate so the previous second player opens");
+    console.log("6. Open seats fill through join_game as usual, listed in");
+    console.log("   the registry when a lobby page is attached");
+  });
+});
@@ -0,0 +1,84 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { sendAndConfirmTransaction } from "@solana/web3.js";
+import { Rummikub } from "../target/types/rummikub";
+import {
+  setupConnections,
+  createTestPlayers,
+  createGamePDAs,
+  airdropToPlayers,
+  ensureConfig,
+  DEFAULT_GAME_OPTIONS,
+} from "./helpers";
+import { assert } from "chai";
+
+describe("20 - Match Mode", () => {
+  const { connection, isLocalnet, providerMagic } = setupConnections();
+  const program: Program<Rummikub> = anchor.workspace.Rummikub;
+  const { player1, player2 } = createTestPlayers();
+  const gameId = new anchor.BN(Date.now());
+  const { gamePDA } = createGamePDAs(program, gameId);
+
+  async function initialize(id: anchor.BN, matchTarget: any) {
+    const tx = await program.methods
+      .initializeGame(id, 2, { ...DEFAULT_GAME_OPTIONS, matchTarget }, null)
+      .accounts({
+        authority: providerMagic.wallet.publicKey,
+      })
+      .transaction();
+
+    await sendAndConfirmTransaction(
+      connection,
+      tx,
+      [providerMagic.wallet.payer],
+      {
+        commitment: "confirmed",
+      }
+    );
+  }
+
+  before(async function () {
+    console.log("\n========== SETUP ==========");
+    await ensureConfig(program, connection, providerMagic);
+    await airdropToPlayers(connection, isLocalnet, [player1, player2]);
+    console.log("===========================\n");
+  });
+
+  it("should reject a match without rounds to play", async () => {
+    try {
+      await initialize(new anchor.BN(Date.now() + 1), {
+        rounds: { rounds: 0 },
+      });
+      assert.fail("a zero-round match should be rejected");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidMatchTarget");
+    }
+  });
+
+  it("should start a best-of-three match", async () => {
+    await initialize(gameId, { rounds: { rounds: 3 } });
+
+    for (const player of [player1, player2]) {
+      const tx = await program.methods
+        .joinGame()
+        .accounts({
+          game: gamePDA,
+          player: player.publicKey,
+        })
+        .transaction();
+
+      await sendAndConfirmTransaction(connection, tx, [player], {
+        skipPreflight: true,
+        commitment: "confirmed",
+      });
+    }
+
+    const gameState = await program.account.gameState.fetch(gamePDA);
+    assert.deepEqual(gameState.matchTarget, { rounds: { rounds: 3 } });
+    assert.equal(gameState.roundsPlayed, 0);
+    assert.deepEqual(gameState.gameStatus, { inProgress: {} });
+    for (const player of gameState.players.slice(0, 2)) {
+      assert.equal(player.matchScore, 0);
+    }
+  });
+});
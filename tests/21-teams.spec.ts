@@ -0,0 +1,99 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { sendAndConfirmTransaction } from "@solana/web3.js";
+import { Rummikub } from "../target/types/rummikub";
+import {
+  setupConnections,
+  createTestPlayers,
+  createGamePDAs,
+  airdropToPlayers,
+  ensureConfig,
+  DEFAULT_GAME_OPTIONS,
+} from "./helpers";
+import { assert } from "chai";
+
+describe("21 - Team Games", () => {
+  const { connection, isLocalnet, providerMagic } = setupConnections();
+  const program: Program<Rummikub> = anchor.workspace.Rummikub;
+  const { player1, player2 } = createTestPlayers();
+  const gameId = new anchor.BN(Date.now());
+  const { gamePDA } = createGamePDAs(program, gameId);
+
+  async function initialize(id: anchor.BN, maxPlayers: number, options: any) {
+    const tx = await program.methods
+      .initializeGame(id, maxPlayers, options, null)
+      .accounts({
+        authority: providerMagic.wallet.publicKey,
+      })
+      .transaction();
+
+    await sendAndConfirmTransaction(
+      connection,
+      tx,
+      [providerMagic.wallet.payer],
+      {
+        commitment: "confirmed",
+      }
+    );
+  }
+
+  before(async function () {
+    console.log("\n========== SETUP ==========");
+    await ensureConfig(program, connection, providerMagic);
+    await airdropToPlayers(connection, isLocalnet, [player1, player2]);
+    console.log("===========================\n");
+  });
+
+  it("should reject a team game without four seats", async () => {
+    try {
+      await initialize(new anchor.BN(Date.now() + 1), 2, {
+        ...DEFAULT_GAME_OPTIONS,
+        teams: true,
+      });
+      assert.fail("a 2-player team game should be rejected");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidTeamGame");
+    }
+  });
+
+  it("should reject partner melds outside a team game", async () => {
+    try {
+      await initialize(new anchor.BN(Date.now() + 2), 4, {
+        ...DEFAULT_GAME_OPTIONS,
+        partnerMelds: true,
+      });
+      assert.fail("partner melds need teams");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidTeamGame");
+    }
+  });
+
+  it("should create a 2v2 game with partner melds", async () => {
+    await initialize(gameId, 4, {
+      ...DEFAULT_GAME_OPTIONS,
+      teams: true,
+      partnerMelds: true,
+    });
+
+    for (const player of [player1, player2]) {
+      const tx = await program.methods
+        .joinGame()
+        .accounts({
+          game: gamePDA,
+          player: player.publicKey,
+        })
+        .transaction();
+
+      await sendAndConfirmTransaction(connection, tx, [player], {
+        skipPreflight: true,
+        commitment: "confirmed",
+      });
+    }
+
+    const gameState = await program.account.gameState.fetch(gamePDA);
+    assert.isTrue(gameState.teams);
+    assert.isTrue(gameState.partnerMelds);
+    assert.equal(gameState.maxPlayers, 4);
+    assert.deepEqual(gameState.gameStatus, { waitingForPlayers: {} });
+  });
+});
@@ -0,0 +1,93 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { sendAndConfirmTransaction } from "@solana/web3.js";
+import { Rummikub } from "../target/types/rummikub";
+import {
+  setupConnections,
+  createTestPlayers,
+  createGamePDAs,
+  airdropToPlayers,
+  ensureConfig,
+  DEFAULT_GAME_OPTIONS,
+  STANDARD_RULES,
+} from "./helpers";
+import { assert } from "chai";
+
+describe("22 - House Rules", () => {
+  const { connection, isLocalnet, providerMagic } = setupConnections();
+  const program: Program<Rummikub> = anchor.workspace.Rummikub;
+  const { player1, player2 } = createTestPlayers();
+  const gameId = new anchor.BN(Date.now());
+  const { gamePDA } = createGamePDAs(program, gameId);
+
+  async function initialize(id: anchor.BN, maxPlayers: number, options: any) {
+    const tx = await program.methods
+      .initializeGame(id, maxPlayers, options, null)
+      .accounts({
+        authority: providerMagic.wallet.publicKey,
+      })
+      .transaction();
+
+    await sendAndConfirmTransaction(
+      connection,
+      tx,
+      [providerMagic.wallet.payer],
+      {
+        commitment: "confirmed",
+      }
+    );
+  }
+
+  before(async function () {
+    console.log("\n========== SETUP ==========");
+    await ensureConfig(program, connection, providerMagic);
+    await airdropToPlayers(connection, isLocalnet, [player1, player2]);
+    console.log("===========================\n");
+  });
+
+  it("should reject rules outside the bounds", async () => {
+    try {
+      await initialize(new anchor.BN(Date.now() + 1), 2, {
+        ...DEFAULT_GAME_OPTIONS,
+        rules: { ...STANDARD_RULES, handSize: 30 },
+      });
+      assert.fail("a 30-tile hand should be rejected");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidRules");
+    }
+  });
+
+  it("should deal hands by the game's rules", async () => {
+    const rules = {
+      initialMeld: 0,
+      handSize: 10,
+      jokers: 4,
+      jokerPenalty: 50,
+      wrapRuns: true,
+    };
+    await initialize(gameId, 2, { ...DEFAULT_GAME_OPTIONS, rules });
+
+    for (const player of [player1, player2]) {
+      const tx = await program.methods
+        .joinGame()
+        .accounts({
+          game: gamePDA,
+          player: player.publicKey,
+        })
+        .transaction();
+
+      await sendAndConfirmTransaction(connection, tx, [player], {
+        skipPreflight: true,
+        commitment: "confirmed",
+      });
+    }
+
+    const gameState = await program.account.gameState.fetch(gamePDA);
+    assert.deepEqual(gameState.rules, rules);
+    for (const player of gameState.players.slice(0, 2)) {
+      assert.equal(player.tileCount, 10);
+    }
+    // 104 number tiles + 4 jokers, minus two hands
+    assert.equal(gameState.tilesRemaining, 108 - 20);
+  });
+});
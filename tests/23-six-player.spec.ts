@@ -0,0 +1,97 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { sendAndConfirmTransaction } from "@solana/web3.js";
+import { Rummikub } from "../target/types/rummikub";
+import {
+  setupConnections,
+  createTestPlayers,
+  createGamePDAs,
+  airdropToPlayers,
+  ensureConfig,
+  DEFAULT_GAME_OPTIONS,
+  STANDARD_RULES,
+} from "./helpers";
+import { assert } from "chai";
+
+describe("23 - Six-Player Edition", () => {
+  const { connection, isLocalnet, providerMagic } = setupConnections();
+  const program: Program<Rummikub> = anchor.workspace.Rummikub;
+  const { player1, player2 } = createTestPlayers();
+  const gameId = new anchor.BN(Date.now());
+  const { gamePDA } = createGamePDAs(program, gameId);
+
+  async function initialize(id: anchor.BN, maxPlayers: number, options: any) {
+    const tx = await program.methods
+      .initializeGame(id, maxPlayers, options, null)
+      .accounts({
+        authority: providerMagic.wallet.publicKey,
+      })
+      .transaction();
+
+    await sendAndConfirmTransaction(
+      connection,
+      tx,
+      [providerMagic.wallet.payer],
+      {
+        commitment: "confirmed",
+      }
+    );
+  }
+
+  before(async function () {
+    console.log("\n========== SETUP ==========");
+    await ensureConfig(program, connection, providerMagic);
+    await airdropToPlayers(connection, isLocalnet, [player1, player2]);
+    console.log("===========================\n");
+  });
+
+  it("should reject more than six seats", async () => {
+    try {
+      await initialize(new anchor.BN(Date.now() + 1), 7, DEFAULT_GAME_OPTIONS);
+      assert.fail("a 7-player game should be rejected");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidPlayerCount");
+    }
+  });
+
+  it("should require the edition's four jokers", async () => {
+    try {
+      await initialize(
+        new anchor.BN(Date.now() + 2),
+        6,
+        DEFAULT_GAME_OPTIONS
+      );
+      assert.fail("a six-player game with two jokers should be rejected");
+    } catch (error) {
+      assert.include(error.toString(), "InvalidRules");
+    }
+  });
+
+  it("should deal from the 160-tile set", async () => {
+    await initialize(gameId, 6, {
+      ...DEFAULT_GAME_OPTIONS,
+      rules: { ...STANDARD_RULES, jokers: 4 },
+    });
+
+    for (const player of [player1, player2]) {
+      const tx = await program.methods
+        .joinGame()
+        .accounts({
+          game: gamePDA,
+          player: player.publicKey,
+        })
+        .transaction();
+
+      await sendAndConfirmTransaction(connection, tx, [player], {
+        skipPreflight: true,
+        commitment: "confirmed",
+      });
+    }
+
+    const gameState = await program.account.gameState.fetch(gamePDA);
+    assert.equal(gameState.maxPlayers, 6);
+    assert.equal(gameState.extraPlayers.length, 2);
+    assert.deepEqual(gameState.gameStatus, { waitingForPlayers: {} });
+    assert.equal(gameState.tilesRemaining, 160 - 2 * 14);
+  });
+});
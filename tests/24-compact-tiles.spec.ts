@@ -0,0 +1,132 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { sendAndConfirmTransaction } from "@solana/web3.js";
+import { Rummikub } from "../target/types/rummikub";
+import {
+  setupConnections,
+  createTestPlayers,
+  createGamePDAs,
+  airdropToPlayers,
+  ensureConfig,
+  DEFAULT_GAME_OPTIONS,
+} from "./helpers";
+import { assert } from "chai";
+
+describe("24 - Compact Tiles", () => {
+  const { connection, isLocalnet, providerMagic } = setupConnections();
+  const program: Program<Rummikub> = anchor.workspace.Rummikub;
+  const { player1, player2 } = createTestPlayers();
+  const gameId = new anchor.BN(Date.now());
+  const { gamePDA } = createGamePDAs(program, gameId);
+
+  // Compute units used by a confirmed transaction, logged so runs against
+  // different builds can be compared
+  async function computeUnits(label: string, signature: string) {
+    const tx = await connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const units = tx.meta.computeUnitsConsumed;
+    console.log(`  ${label}: ${units} CU`);
+    return units;
+  }
+
+  before(async function () {
+    console.log("\n========== SETUP ==========");
+    await ensureConfig(program, connection, providerMagic);
+    await airdropToPlayers(connection, isLocalnet, [player1, player2]);
+
+    const tx = await program.methods
+      .initializeGame(gameId, 2, DEFAULT_GAME_OPTIONS, null)
+      .accounts({
+        authority: providerMagic.wallet.publicKey,
+      })
+      .transaction();
+
+    await sendAndConfirmTransaction(
+      connection,
+      tx,
+      [providerMagic.wallet.payer],
+      {
+        commitment: "confirmed",
+      }
+    );
+    console.log("===========================\n");
+  });
+
+  it("should size a 2-4 player game for one-byte tiles", async () => {
+    const account = await connection.getAccountInfo(gamePDA);
+    // 1306 bytes when every tile took 3
+    assert.isBelow(account.data.length, 1200);
+
+    // Layout version, the last byte of the discriminator
+    assert.equal(account.data[8], 1);
+  });
+
+  it("should measure join and draw", async () => {
+    for (const player of [player1, player2]) {
+      const tx = await program.methods
+        .joinGame()
+        .accounts({
+          game: gamePDA,
+          player: player.publicKey,
+        })
+        .transaction();
+
+      const signature = await sendAndConfirmTransaction(
+        connection,
+        tx,
+        [player],
+        {
+          commitment: "confirmed",
+        }
+      );
+      await computeUnits("join_game", signature);
+    }
+
+    const gameState = await program.account.gameState.fetch(gamePDA);
+    const player = gameState.currentTurn === 0 ? player1 : player2;
+    const tx = await program.methods
+      .drawTile()
+      .accounts({
+        game: gamePDA,
+        player: player.publicKey,
+      })
+      .transaction();
+
+    const signature = await sendAndConfirmTransaction(
+      connection,
+      tx,
+      [player],
+      {
+        commitment: "confirmed",
+      }
+    );
+    await computeUnits("draw_tile", signature);
+  });
+
+  it("should refuse to migrate a game already in the current layout", async () => {
+    const tx = await program.methods
+      .migrateGame()
+      .accounts({
+        game: gamePDA,
+        escrow: null,
+        payer: providerMagic.wallet.publicKey,
+      })
+      .transaction();
+
+    try {
+      await sendAndConfirmTransaction(
+        connection,
+        tx,
+        [providerMagic.wallet.payer],
+        {
+          commitment: "confirmed",
+        }
+      );
+      assert.fail("migrate_game should reject a current-layout game");
+    } catch (error) {
+      assert.include(error.toString(), "NothingToMigrate");
+    }
+  });
+});
@@ -6,8 +6,45 @@ import { ConnectionMagicRouter } from "@magicblock-labs/ephemeral-rollups-sdk";
 
 export const GAME_SEED = "game";
 export const TREASURY_SEED = "treasury";
+export const CONFIG_SEED = "config";
+export const ESCROW_SEED = "escrow";
+export const BANKROLL_SEED = "bankroll";
+export const SESSION_SEED = "session";
+export const PROFILE_SEED = "profile";
+export const SEASON_SEED = "season";
+export const TOURNAMENT_SEED = "tournament";
+export const QUEUE_SEED = "queue";
+export const LOBBY_SEED = "lobby";
+export const COUNTER_SEED = "counter";
 export const ENTRY_FEE = 0.1 * LAMPORTS_PER_SOL; // 0.1 SOL
 
+// The standard rule set (GameRules::default)
+export const STANDARD_RULES = {
+  initialMeld: 30,
+  handSize: 14,
+  jokers: 2,
+  jokerPenalty: 30,
+  wrapRuns: false,
+};
+
+// Options passed to initialize_game by suites that do not test them
+export const DEFAULT_GAME_OPTIONS = {
+  rated: false,
+  matchTarget: null,
+  teams: false,
+  partnerMelds: false,
+  rules: STANDARD_RULES,
+};
+
+// ER validators games may be delegated to (see README)
+export const KNOWN_VALIDATORS = [
+  "MAS1Dt9qreoRMQ14YQuhg8UTZMMzDdKhmkZMECCzk57",
+  "MEUGGrYPxKk17hCr7wpT6s8dtNokZj5U2L57vjYMS8e",
+  "MUS3hc9TCw4cGC12vHNoYcCGzJG1txjgQLZWVoeNHNd",
+  "FnE6VJT5QNZdedZPnCoLsARgBwoE6DeJNjBs2H1gySXA",
+  "mAGicPQYBMvcYveUZA5F5UNNwyHvfYh5xkLS2Fr1mev",
+].map((key) => new web3.PublicKey(key));
+
 export interface TestContext {
   connection: any;
   erConnection: any;
@@ -98,6 +135,7 @@ export function createGamePDAs(
   gameId: anchor.BN
 ): {
   gamePDA: web3.PublicKey;
+  escrowPDA: web3.PublicKey;
   treasuryPDA: web3.PublicKey;
 } {
   const [gamePDA] = anchor.web3.PublicKey.findProgramAddressSync(
@@ -105,12 +143,17 @@ export function createGamePDAs(
     program.programId
   );
 
+  const [escrowPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from(ESCROW_SEED), gameId.toArrayLike(Buffer, "le", 8)],
+    program.programId
+  );
+
   const [treasuryPDA] = anchor.web3.PublicKey.findProgramAddressSync(
     [Buffer.from(TREASURY_SEED)],
     program.programId
   );
 
-  return { gamePDA, treasuryPDA };
+  return { gamePDA, escrowPDA, treasuryPDA };
 }
 
 export async function setupEphemeralValidator(
@@ -154,3 +197,183 @@ export async function airdropToPlayers(
   }
   console.log("Airdrops complete\n");
 }
+
+export function getConfigPDA(program: Program<Rummikub>): web3.PublicKey {
+  const [configPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from(CONFIG_SEED)],
+    program.programId
+  );
+  return configPDA;
+}
+
+export async function ensureConfig(
+  program: Program<Rummikub>,
+  connection: any,
+  provider: anchor.AnchorProvider
+): Promise<web3.PublicKey> {
+  const configPDA = getConfigPDA(program);
+  const payer = (provider.wallet as anchor.Wallet).payer;
+  const existing = await connection.getAccountInfo(configPDA);
+
+  if (!existing) {
+    // The config can only be created by the program upgrade authority
+    const [programData] = anchor.web3.PublicKey.findProgramAddressSync(
+      [program.programId.toBuffer()],
+      new web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+    );
+
+    const tx = await program.methods
+      .initializeConfig()
+      .accounts({
+        admin: provider.wallet.publicKey,
+        programData,
+      })
+      .transaction();
+
+    await web3.sendAndConfirmTransaction(connection, tx, [payer], {
+      skipPreflight: true,
+      commitment: "confirmed",
+    });
+  }
+
+  // Games can only be delegated to allowlisted validators
+  const config = await program.account.config.fetch(configPDA);
+  const missing = KNOWN_VALIDATORS.some(
+    (validator) =>
+      !config.allowedValidators.some((allowed) => allowed.equals(validator))
+  );
+  if (missing) {
+    const tx = await program.methods
+      .setValidators(KNOWN_VALIDATORS)
+      .accounts({
+        admin: provider.wallet.publicKey,
+      })
+      .transaction();
+
+    await web3.sendAndConfirmTransaction(connection, tx, [payer], {
+      skipPreflight: true,
+      commitment: "confirmed",
+    });
+  }
+  return configPDA;
+}
+
+export function getBankrollPDA(
+  program: Program<Rummikub>,
+  owner: web3.PublicKey
+): web3.PublicKey {
+  const [bankrollPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from(BANKROLL_SEED), owner.toBuffer()],
+    program.programId
+  );
+  return bankrollPDA;
+}
+
+export function getSessionPDA(
+  program: Program<Rummikub>,
+  game: web3.PublicKey,
+  player: web3.PublicKey
+): web3.PublicKey {
+  const [sessionPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from(SESSION_SEED), game.toBuffer(), player.toBuffer()],
+    program.programId
+  );
+  return sessionPDA;
+}
+
+export function getProfilePDA(
+  program: Program<Rummikub>,
+  owner: web3.PublicKey
+): web3.PublicKey {
+  const [profilePDA] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from(PROFILE_SEED), owner.toBuffer()],
+    program.programId
+  );
+  return profilePDA;
+}
+
+export function getSeasonPDA(
+  program: Program<Rummikub>,
+  seasonId: anchor.BN
+): web3.PublicKey {
+  const [seasonPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from(SEASON_SEED), seasonId.toArrayLike(Buffer, "le", 8)],
+    program.programId
+  );
+  return seasonPDA;
+}
+
+export function getTournamentPDA(
+  program: Program<Rummikub>,
+  tournamentId: anchor.BN
+): web3.PublicKey {
+  const [tournamentPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from(TOURNAMENT_SEED), tournamentId.toArrayLike(Buffer, "le", 8)],
+    program.programId
+  );
+  return tournamentPDA;
+}
+
+export function getQueuePDA(
+  program: Program<Rummikub>,
+  stake: anchor.BN,
+  playerCount: number
+): web3.PublicKey {
+  const [queuePDA] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(QUEUE_SEED),
+      stake.toArrayLike(Buffer, "le", 8),
+      Buffer.from([playerCount]),
+    ],
+    program.programId
+  );
+  return queuePDA;
+}
+
+export function getLobbyPDA(
+  program: Program<Rummikub>,
+  page: number
+): web3.PublicKey {
+  const pageBytes = Buffer.alloc(4);
+  pageBytes.writeUInt32LE(page);
+  const [lobbyPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from(LOBBY_SEED), pageBytes],
+    program.programId
+  );
+  return lobbyPDA;
+}
+
+export function getCounterPDA(program: Program<Rummikub>): web3.PublicKey {
+  const [counterPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from(COUNTER_SEED)],
+    program.programId
+  );
+  return counterPDA;
+}
+
+export async function ensureGameCounter(
+  program: Program<Rummikub>,
+  connection: any,
+  provider: anchor.AnchorProvider
+): Promise<web3.PublicKey> {
+  const counterPDA = getCounterPDA(program);
+  if (!(await connection.getAccountInfo(counterPDA))) {
+    const tx = await program.methods
+      .initializeGameCounter()
+      .accounts({
+        payer: provider.wallet.publicKey,
+      })
+      .transaction();
+
+    await web3.sendAndConfirmTransaction(
+      connection,
+      tx,
+      [(provider.wallet as anchor.Wallet).payer],
+      {
+        skipPreflight: true,
+        commitment: "confirmed",
+      }
+    );
+  }
+  return counterPDA;
+}